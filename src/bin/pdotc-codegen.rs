@@ -0,0 +1,31 @@
+//! Generates a `SubstrateNetwork` impl and a proxy type enum from a runtime's
+//! metadata, read offline from a file holding the SCALE encoded metadata or
+//! the hex string returned by `state_getMetadata`.
+//!
+//! ```text
+//! cargo run --features metadata --bin pdotc-codegen -- \
+//!     asset_hub_polkadot.scale AssetHubPolkadot asset-hub-polkadot | rustfmt
+//! ```
+
+use std::error::Error;
+use std::{env, fs, process};
+
+use pdotc::metadata::codegen::generate;
+use pdotc::metadata::RuntimeCalls;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    let [_, path, network, name] = args.as_slice() else {
+        eprintln!("Usage: pdotc-codegen <metadata file> <network struct> <network name>");
+        process::exit(1);
+    };
+
+    let bytes = fs::read(path)?;
+    let metadata = match bytes.strip_prefix(b"0x") {
+        Some(hex) => hex::decode(hex.trim_ascii())?,
+        None => bytes,
+    };
+    let runtime = RuntimeCalls::decode(&metadata)?;
+    print!("{}", generate(&runtime, network, name));
+    Ok(())
+}
@@ -1,14 +1,22 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use parity_scale_codec::Decode;
 use sp_core::crypto::{AccountId32, UncheckedFrom};
 pub use sp_core::ecdsa::Signature;
+use sp_core::storage::{StorageChangeSet, StorageKey};
 
-use crate::network::{Kusama, Polkadot, Polymesh, SubstrateNetwork, Westend};
-use crate::pallets::storage::storage_key_account_balance;
+use crate::network::{
+    AssetHubKusama, AssetHubPolkadot, AssetHubWestend, Kusama, PeopleKusama, PeoplePolkadot,
+    PeopleWestend, Polkadot, Polymesh, SubstrateNetwork, Westend,
+};
+use crate::pallets::storage::{storage_key_account_balance, storage_value_key};
+use crate::pallets::system::TransferWithMemo;
+use crate::pallets::xcm_pallet::Location;
 use crate::rpc::{
     chain_get_block, chain_get_block_hash, chain_get_genesis_hash, payment_query_fee_details,
-    state_get_runtime_version, state_get_storage, JsonRpcError, RpcClient,
+    state_get_keys_paged, state_get_runtime_version, state_get_runtime_version_at,
+    state_get_storage, state_query_storage_at, JsonRpcError, RpcClient,
 };
 use crate::utils::FromHexString;
 use crate::{
@@ -17,6 +25,10 @@ use crate::{
 
 pub type Result<R, E = ClientError> = std::result::Result<R, E>;
 
+/// Number of keys requested per `state_getKeysPaged` or
+/// `state_queryStorageAt` call
+const KEYS_PAGE_SIZE: u32 = 1000;
+
 pub type StdError = Box<dyn std::error::Error + Send + Sync>;
 
 #[derive(Debug, thiserror::Error)]
@@ -41,6 +53,11 @@ pub enum ClientError {
     Other(#[from] StdError),
     #[error("Invalid signature size")]
     InvalidSignatureSize,
+    #[error("Unknown runtime spec version {0}, no call index table covers it")]
+    UnknownSpecVersion(u32),
+    #[cfg(feature = "metadata")]
+    #[error("Unsupported metadata version V{0}, expecting V14 or V15")]
+    UnsupportedMetadataVersion(u32),
 }
 
 /// A trait to implement on a keystore that can produce a signature
@@ -98,6 +115,58 @@ impl<'c> ApiBuilder {
         }
     }
 
+    pub fn people_polkadot<C: RpcClient>(
+        client: &'c C,
+    ) -> ApiBuilderWithClient<'c, C, PeoplePolkadot> {
+        ApiBuilderWithClient {
+            client,
+            network: PhantomData,
+        }
+    }
+
+    pub fn people_kusama<C: RpcClient>(client: &'c C) -> ApiBuilderWithClient<'c, C, PeopleKusama> {
+        ApiBuilderWithClient {
+            client,
+            network: PhantomData,
+        }
+    }
+
+    pub fn people_westend<C: RpcClient>(
+        client: &'c C,
+    ) -> ApiBuilderWithClient<'c, C, PeopleWestend> {
+        ApiBuilderWithClient {
+            client,
+            network: PhantomData,
+        }
+    }
+
+    pub fn asset_hub_polkadot<C: RpcClient>(
+        client: &'c C,
+    ) -> ApiBuilderWithClient<'c, C, AssetHubPolkadot> {
+        ApiBuilderWithClient {
+            client,
+            network: PhantomData,
+        }
+    }
+
+    pub fn asset_hub_kusama<C: RpcClient>(
+        client: &'c C,
+    ) -> ApiBuilderWithClient<'c, C, AssetHubKusama> {
+        ApiBuilderWithClient {
+            client,
+            network: PhantomData,
+        }
+    }
+
+    pub fn asset_hub_westend<C: RpcClient>(
+        client: &'c C,
+    ) -> ApiBuilderWithClient<'c, C, AssetHubWestend> {
+        ApiBuilderWithClient {
+            client,
+            network: PhantomData,
+        }
+    }
+
     pub fn builder<C: RpcClient, N: SubstrateNetwork>(client: &'c C) -> ApiBuilderWithClient<C, N> {
         ApiBuilderWithClient {
             client,
@@ -117,6 +186,7 @@ impl<'c, C: RpcClient, N: SubstrateNetwork> ApiBuilderWithClient<'c, C, N> {
             client: self.client,
             network: PhantomData,
             signer: Some(signer),
+            fee_asset: None,
         }
     }
 
@@ -125,6 +195,7 @@ impl<'c, C: RpcClient, N: SubstrateNetwork> ApiBuilderWithClient<'c, C, N> {
             client: self.client,
             network: PhantomData,
             signer: None,
+            fee_asset: None,
         }
         .build()
     }
@@ -134,9 +205,16 @@ pub struct ApiBuilderWithClientAndSigner<'c, S, C: RpcClient, N: SubstrateNetwor
     client: &'c C,
     network: PhantomData<N>,
     signer: Option<S>,
+    fee_asset: Option<Location>,
 }
 
 impl<'c, S, C: RpcClient, N: SubstrateNetwork> ApiBuilderWithClientAndSigner<'c, S, C, N> {
+    /// Pay fees in the asset at `asset_id`, see [`Api::set_fee_asset`]
+    pub fn fee_asset(mut self, asset_id: Location) -> Self {
+        self.fee_asset = Some(asset_id);
+        self
+    }
+
     pub fn build(self) -> Result<Api<'c, S, C, N>> {
         let genesis_hash = genesis_hash(self.client)?;
         let runtime_version = runtime_version(self.client)?;
@@ -144,6 +222,7 @@ impl<'c, S, C: RpcClient, N: SubstrateNetwork> ApiBuilderWithClientAndSigner<'c,
             genesis_hash,
             runtime_version,
             signer: self.signer,
+            fee_asset: self.fee_asset,
             client: self.client,
             network: PhantomData,
         })
@@ -165,11 +244,21 @@ pub struct Api<'c, S, C: RpcClient, Network: SubstrateNetwork> {
     pub(crate) genesis_hash: H256,
     pub(crate) runtime_version: RuntimeVersion,
     pub signer: Option<S>,
+    pub(crate) fee_asset: Option<Location>,
     pub(crate) client: &'c C,
     network: PhantomData<Network>,
 }
 
 impl<'c, S, C: RpcClient, N: SubstrateNetwork> Api<'c, S, C, N> {
+    /// Pay the fees of extrinsics created from now on in the asset at
+    /// `asset_id`, e.g.
+    /// [`asset_location`](crate::pallets::assets::asset_location), or in the native token if
+    /// `None`. Only networks charging fees with `ChargeAssetTxPayment`
+    /// support this, others always pay in the native token.
+    pub fn set_fee_asset(&mut self, asset_id: Option<Location>) {
+        self.fee_asset = asset_id;
+    }
+
     /// Get balances of given address
     /// Returns None because the account can not exist
     pub fn account_data<A: Into<AccountId32>>(
@@ -201,6 +290,27 @@ impl<'c, S, C: RpcClient, N: SubstrateNetwork> Api<'c, S, C, N> {
         self.client.post(chain_get_block(hash))?.into_result()
     }
 
+    /// Get the version of the runtime at block `hash` or at the current
+    /// block if `None`
+    pub fn runtime_version_at(&self, hash: Option<H256>) -> Result<RuntimeVersion> {
+        self.client
+            .post(state_get_runtime_version_at(hash))?
+            .into_result()
+    }
+
+    /// Get the transfers in block `hash` that were batched with a memo
+    /// remark, decoded with the call indices of the runtime in effect at that
+    /// block
+    pub fn block_transfers_with_memo(&self, hash: Option<H256>) -> Result<Vec<TransferWithMemo>> {
+        let hash = match hash {
+            Some(hash) => hash,
+            None => self.block_hash(None)?,
+        };
+        let block = self.block(Some(hash))?.block;
+        let runtime_version = self.runtime_version_at(Some(hash))?;
+        block.transfers_with_memo::<N>(runtime_version.spec_version)
+    }
+
     /// Gets block hash of block `number` or current block if `number` is `None`
     pub fn block_hash(&self, number: Option<u32>) -> Result<H256> {
         self.client
@@ -208,6 +318,14 @@ impl<'c, S, C: RpcClient, N: SubstrateNetwork> Api<'c, S, C, N> {
             .into_result()
     }
 
+    /// Gets the number of block `hash` or of the current block if `hash` is
+    /// `None`
+    pub fn block_number(&self, hash: Option<H256>) -> Result<u32> {
+        let storage_key = storage_value_key("System", "Number");
+        let json = state_get_storage(storage_key, hash);
+        Ok(self.client.post(json)?.decode_into()?.unwrap_or_default())
+    }
+
     /// Calculate a fee for given extrinsic
     pub fn fee_details(&self, xt_hex_prefixed: &str, at_block: Option<H256>) -> Result<FeeDetails> {
         let jsonreq = payment_query_fee_details(xt_hex_prefixed, at_block);
@@ -215,4 +333,43 @@ impl<'c, S, C: RpcClient, N: SubstrateNetwork> Api<'c, S, C, N> {
 
         Ok(fees)
     }
+
+    /// Get all storage keys starting with `prefix`, paging through
+    /// `state_getKeysPaged`
+    pub(crate) fn storage_keys(
+        &self,
+        prefix: StorageKey,
+        at_block: Option<H256>,
+    ) -> Result<Vec<StorageKey>> {
+        let mut keys: Vec<StorageKey> = Vec::new();
+        loop {
+            let json = state_get_keys_paged(&prefix, KEYS_PAGE_SIZE, keys.last(), at_block);
+            let page: Vec<StorageKey> = self.client.post(json)?.into_result()?;
+            let done = page.len() < KEYS_PAGE_SIZE as usize;
+            keys.extend(page);
+            if done {
+                return Ok(keys);
+            }
+        }
+    }
+
+    /// Get the decoded values of `keys`, fetched `KEYS_PAGE_SIZE` at a time
+    /// with `state_queryStorageAt`. Keys without a value are left out.
+    pub(crate) fn storage_values<T: Decode>(
+        &self,
+        keys: &[StorageKey],
+        at_block: Option<H256>,
+    ) -> Result<Vec<(StorageKey, T)>> {
+        let mut values = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(KEYS_PAGE_SIZE as usize) {
+            let json = state_query_storage_at(chunk, at_block);
+            let change_sets: Vec<StorageChangeSet<H256>> = self.client.post(json)?.into_result()?;
+            for (key, data) in change_sets.into_iter().flat_map(|c| c.changes) {
+                if let Some(data) = data {
+                    values.push((key, T::decode(&mut data.0.as_slice())?));
+                }
+            }
+        }
+        Ok(values)
+    }
 }
@@ -2,18 +2,24 @@ use std::str::FromStr;
 
 use base58::ToBase58;
 use blake2::{Blake2b512, Digest};
-use parity_scale_codec::{Compact, Decode, Encode, Error, Input};
+use parity_scale_codec::{Compact, CompactAs, Decode, Encode, Error, Input, Output};
 use serde::{Deserialize, Serialize};
 pub use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58AddressFormatRegistry};
 pub use sp_core::ecdsa::{Public as EcdsaPublic, Signature as EcdsaSignature};
 pub use sp_core::ed25519::{Public as Ed25519Public, Signature as Ed25519Signature};
+use sp_core::U256;
 pub use sp_core::{blake2_256, H256};
 
+use crate::network::SubstrateNetwork;
+use crate::pallets::system::{decode_transfers_with_memo, TransferWithMemo};
 use crate::pallets::timestamp::decode_timestamp;
+use crate::pallets::xcm_pallet::Location;
 use crate::ss58::Ss58Codec;
 use crate::utils::deser_number_or_hex;
 
 pub mod client;
+#[cfg(feature = "metadata")]
+pub mod metadata;
 pub mod network;
 pub mod pallets;
 pub mod rpc;
@@ -124,15 +130,85 @@ impl From<Ed25519Signature> for MultiSignature {
 /// current hash, ...others
 pub type SignedExtra = (u32, u32, H256, H256, (), (), ());
 
-#[derive(Clone, Copy, Debug, Decode, Encode, PartialEq, Eq)]
-pub struct GenericExtra(Era, Compact<u32>, Compact<Balance>);
+/// Not `Copy`, as the fee payment of networks charging fees with
+/// `ChargeAssetTxPayment` holds the `Location` of an asset.
+#[derive(Clone, Debug, Decode, Encode, PartialEq, Eq)]
+pub struct GenericExtra(Era, Compact<u32>, TxPayment);
 
 impl GenericExtra {
     pub fn new(era: Era, nonce: u32) -> GenericExtra {
-        GenericExtra(era, Compact(nonce), Compact(0u128))
+        GenericExtra(era, Compact(nonce), TxPayment::Native(0))
+    }
+
+    /// Extra of networks charging fees with `ChargeAssetTxPayment`, paying
+    /// them in `asset_id` or in the native token if `None`
+    pub fn with_asset_payment(era: Era, nonce: u32, asset_id: Option<Location>) -> GenericExtra {
+        GenericExtra(
+            era,
+            Compact(nonce),
+            TxPayment::Asset(ChargeAssetTxPayment { tip: 0, asset_id }),
+        )
+    }
+
+    /// Decodes the extra of an extrinsic of network `N`, with the fee payment
+    /// extension `N` charges fees with
+    pub fn decode_for<N: SubstrateNetwork, I: Input>(input: &mut I) -> Result<Self, Error> {
+        Ok(GenericExtra(
+            Decode::decode(input)?,
+            Decode::decode(input)?,
+            TxPayment::decode_for::<N, I>(input)?,
+        ))
+    }
+}
+
+/// The signed extension paying the transaction fee
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TxPayment {
+    /// `ChargeTransactionPayment`, the tip
+    Native(Balance),
+    /// `ChargeAssetTxPayment`
+    Asset(ChargeAssetTxPayment),
+}
+
+impl Encode for TxPayment {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        match self {
+            TxPayment::Native(tip) => Compact(*tip).encode_to(output),
+            TxPayment::Asset(payment) => payment.encode_to(output),
+        }
+    }
+}
+
+impl TxPayment {
+    /// Decodes `ChargeAssetTxPayment` if `N` charges fees with it,
+    /// `ChargeTransactionPayment` otherwise
+    pub fn decode_for<N: SubstrateNetwork, I: Input>(input: &mut I) -> Result<Self, Error> {
+        Ok(if N::CHARGE_ASSET_TX_PAYMENT {
+            TxPayment::Asset(Decode::decode(input)?)
+        } else {
+            TxPayment::Native(Compact::<Balance>::decode(input)?.0)
+        })
     }
 }
 
+impl Decode for TxPayment {
+    /// Both extensions start with the tip, so `ChargeAssetTxPayment` can not
+    /// be told apart and is decoded as `ChargeTransactionPayment`. Use
+    /// [`TxPayment::decode_for`] to decode the extension of a network.
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        Ok(TxPayment::Native(Compact::<Balance>::decode(input)?.0))
+    }
+}
+
+/// Redefinition from `pallet-asset-conversion-tx-payment`. Pays the fee in
+/// the asset at `asset_id` instead of the native token.
+#[derive(Clone, Debug, Decode, Encode, PartialEq, Eq)]
+pub struct ChargeAssetTxPayment {
+    #[codec(compact)]
+    pub tip: Balance,
+    pub asset_id: Option<Location>,
+}
+
 #[derive(Clone, Copy, Debug, Decode, Encode, PartialEq, Eq)]
 pub enum Era {
     Immortal,
@@ -179,7 +255,27 @@ impl<Call> Decode for UncheckedExtrinsic<Call>
 where
     Call: Decode + Encode,
 {
+    /// Decodes the fee payment extension as `ChargeTransactionPayment`, use
+    /// [`UncheckedExtrinsic::decode_for`] for networks charging fees with
+    /// `ChargeAssetTxPayment`
     fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        Self::decode_with(input, GenericExtra::decode)
+    }
+}
+
+impl<Call> UncheckedExtrinsic<Call>
+where
+    Call: Decode + Encode,
+{
+    /// Decodes an extrinsic of network `N`, see [`GenericExtra::decode_for`]
+    pub fn decode_for<N: SubstrateNetwork, I: Input>(input: &mut I) -> Result<Self, Error> {
+        Self::decode_with(input, GenericExtra::decode_for::<N, I>)
+    }
+
+    fn decode_with<I: Input>(
+        input: &mut I,
+        decode_extra: fn(&mut I) -> Result<GenericExtra, Error>,
+    ) -> Result<Self, Error> {
         // This is a little more complicated than usual since the binary format must be
         // compatible with substrate's generic `Vec<u8>` type. Basically this
         // just means accepting that there will be a prefix of vector length (we
@@ -196,7 +292,11 @@ where
 
         Ok(UncheckedExtrinsic {
             signature: if is_signed {
-                Some(Decode::decode(input)?)
+                Some((
+                    Decode::decode(input)?,
+                    Decode::decode(input)?,
+                    decode_extra(input)?,
+                ))
             } else {
                 None
             },
@@ -229,7 +329,8 @@ fn encode_with_vec_prefix<T: Encode, F: Fn(&mut Vec<u8>)>(encoder: F) -> Vec<u8>
     v
 }
 
-#[derive(Clone, Copy, Debug, Encode)]
+/// Not `Copy`, see [`GenericExtra`]
+#[derive(Clone, Debug, Encode)]
 pub struct SignedPayload<Call>((Call, GenericExtra, SignedExtra));
 
 impl<Call: Encode> SignedPayload<Call> {
@@ -258,6 +359,91 @@ pub struct RuntimeVersion {
     pub transaction_version: u32,
 }
 
+/// Redefinition from `sp-weights`.
+#[derive(Clone, Copy, Debug, Default, Decode, Encode, PartialEq, Eq)]
+pub struct Weight {
+    /// The weight of computational time used based on some reference hardware.
+    #[codec(compact)]
+    pub ref_time: u64,
+    /// The weight of storage space used by proof of validity.
+    #[codec(compact)]
+    pub proof_size: u64,
+}
+
+impl Weight {
+    pub fn from_parts(ref_time: u64, proof_size: u64) -> Self {
+        Self {
+            ref_time,
+            proof_size,
+        }
+    }
+}
+
+/// Redefinition from `sp-arithmetic`. A fixed point representation of a
+/// number in the range [0, 1] with a billionth precision.
+#[derive(Clone, Copy, Debug, Default, Decode, Encode, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Perbill(pub u32);
+
+impl Perbill {
+    pub const ACCURACY: u32 = 1_000_000_000;
+
+    pub fn from_parts(parts: u32) -> Self {
+        Self(parts.min(Self::ACCURACY))
+    }
+
+    pub fn from_percent(percent: u32) -> Self {
+        Self::from_parts(percent.min(100) * (Self::ACCURACY / 100))
+    }
+
+    /// Approximation of `p / q`, rounded down. `p` larger than `q` saturates
+    /// to 100%.
+    pub fn from_rational(p: u128, q: u128) -> Self {
+        if q == 0 {
+            return Self(0);
+        }
+        let parts = U256::from(p.min(q)) * Self::ACCURACY / q;
+        Self(parts.as_u32())
+    }
+
+    pub fn deconstruct(self) -> u32 {
+        self.0
+    }
+}
+
+impl std::ops::Mul<Balance> for Perbill {
+    type Output = Balance;
+
+    /// Rounds to the nearest value, preferring down on ties, like
+    /// `sp-arithmetic` does.
+    fn mul(self, b: Balance) -> Balance {
+        let accuracy = U256::from(Self::ACCURACY);
+        let product = U256::from(b) * self.0;
+        let mut result = product / accuracy;
+        if product % accuracy > accuracy / 2 {
+            result += U256::one();
+        }
+        result.as_u128()
+    }
+}
+
+impl CompactAs for Perbill {
+    type As = u32;
+
+    fn encode_as(&self) -> &u32 {
+        &self.0
+    }
+
+    fn decode_from(x: u32) -> Result<Self, Error> {
+        Ok(Self(x))
+    }
+}
+
+impl From<Compact<Perbill>> for Perbill {
+    fn from(x: Compact<Perbill>) -> Self {
+        x.0
+    }
+}
+
 /// Redefinition from `pallet-balances`.
 #[derive(Clone, Eq, PartialEq, Default, Debug, Encode, Decode, Deserialize)]
 pub struct AccountDataGen<Balance> {
@@ -306,6 +492,11 @@ impl ExtraFlags {
     pub fn is_new_logic(&self) -> bool {
         (self.0 & IS_NEW_LOGIC) == IS_NEW_LOGIC
     }
+    /// Accounts not migrated to the new logic store `fee_frozen` in place of
+    /// the flags, and `misc_frozen` in place of `frozen`
+    pub fn old_fee_frozen(&self) -> Option<Balance> {
+        (!self.is_new_logic()).then_some(self.0)
+    }
 }
 
 /// Type used to encode the number of references an account has.
@@ -410,6 +601,19 @@ impl Block {
     pub fn timestamp(&self) -> Option<u64> {
         self.extrinsics.iter().find_map(|e| decode_timestamp(e))
     }
+
+    /// Get the transfers in the Block that were batched with a memo remark.
+    /// `spec_version` is the version of the runtime the Block was built with.
+    pub fn transfers_with_memo<N: SubstrateNetwork>(
+        &self,
+        spec_version: u32,
+    ) -> client::Result<Vec<TransferWithMemo>> {
+        let mut transfers = Vec::new();
+        for xt in &self.extrinsics {
+            transfers.extend(decode_transfers_with_memo::<N>(xt, spec_version)?);
+        }
+        Ok(transfers)
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,1454 @@
+//! Checks the pallet and call indices of a [`SubstrateNetwork`] against the
+//! metadata of a runtime.
+//!
+//! The metadata is either fetched from a node with
+//! [`Api::verify_metadata`], or read from a recorded `state_getMetadata`
+//! result so the check can run offline:
+//!
+//! ```no_run
+//! use pdotc::metadata::{verify_network, RuntimeCalls};
+//! use pdotc::network::Polkadot;
+//!
+//! let bytes = std::fs::read("polkadot_metadata.scale").unwrap();
+//! let calls = RuntimeCalls::decode(&bytes).unwrap();
+//! let report = verify_network::<Polkadot>(&calls, 1_003_000).unwrap();
+//! assert!(report.is_ok(), "{report}");
+//! ```
+//!
+//! Calls are matched by name and index, and their shape only by the number of
+//! arguments: an argument that changed type is not reported.
+
+use std::fmt;
+use std::str::FromStr;
+
+use frame_metadata::v14::{PalletCallMetadata, PalletConstantMetadata};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use parity_scale_codec::{Compact, Decode, Encode};
+use scale_info::form::PortableForm;
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive, Variant};
+
+use crate::client::{Api, ClientError, Result};
+use crate::network::{
+    call_index, AssetHubKusama, AssetHubPolkadot, AssetHubWestend, HasAssets, HasConvictionVoting,
+    HasFastUnstake, HasIdentity, HasIndices, HasMultisig, HasNominationPools, HasPolymeshAssets,
+    HasPolymeshIdentity, HasProxy, HasReferenda, HasStaking, HasSubstrateStaking, HasVesting,
+    HasVoterList, HasXcm, Kusama, PeopleKusama, PeoplePolkadot, PeopleWestend, Polkadot, Polymesh,
+    SubstrateNetwork, Westend,
+};
+use crate::pallets::referenda::TrackId;
+use crate::pallets::voter_list::Score;
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_metadata, RpcClient};
+use crate::utils::FromHexString;
+use crate::{Balance, H256};
+
+pub mod codegen;
+
+/// A call of a pallet
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CallInfo {
+    pub name: String,
+    pub index: u8,
+    /// Number of arguments of the call
+    pub args: usize,
+}
+
+/// A pallet and its calls
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PalletCalls {
+    pub name: String,
+    pub index: u8,
+    pub calls: Vec<CallInfo>,
+}
+
+/// The parts of a runtime's metadata pdotc depends on: the pallet and call
+/// tables, the proxy types, the existential deposit and the referenda tracks
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RuntimeCalls {
+    pub pallets: Vec<PalletCalls>,
+    /// Variants of the `proxy_type` argument of `Proxy::add_proxy`, with
+    /// their indices
+    pub proxy_types: Vec<(String, u8)>,
+    /// `Balances::ExistentialDeposit`
+    pub existential_deposit: Option<Balance>,
+    /// Identifiers of the signed extensions, e.g. `ChargeAssetTxPayment`
+    pub signed_extensions: Vec<String>,
+    /// The signed extensions that add to the extrinsic or to its signed
+    /// payload, in order
+    pub encoded_extensions: Vec<String>,
+    /// Ids and names of `Referenda::Tracks`
+    pub referenda_tracks: Vec<(TrackId, String)>,
+    /// `VoterList::BagThresholds`
+    pub bag_thresholds: Vec<Score>,
+}
+
+/// A pallet of V14 or V15 metadata
+struct PalletRef<'a> {
+    name: &'a str,
+    index: u8,
+    calls: Option<&'a PalletCallMetadata<PortableForm>>,
+    constants: &'a [PalletConstantMetadata<PortableForm>],
+}
+
+impl RuntimeCalls {
+    /// Decodes V14 or V15 metadata, as returned by `state_getMetadata`
+    pub fn decode(metadata: &[u8]) -> Result<Self> {
+        macro_rules! from_metadata {
+            ($m:expr) => {
+                Self::from_pallets(
+                    &$m.types,
+                    $m.pallets.iter().map(|p| PalletRef {
+                        name: &p.name,
+                        index: p.index,
+                        calls: p.calls.as_ref(),
+                        constants: &p.constants,
+                    }),
+                    $m.extrinsic
+                        .signed_extensions
+                        .iter()
+                        .map(|e| (e.identifier.clone(), e.ty.id, e.additional_signed.id))
+                        .collect(),
+                )
+            };
+        }
+
+        let prefixed = RuntimeMetadataPrefixed::decode(&mut &metadata[..])?;
+        match prefixed.1 {
+            RuntimeMetadata::V14(m) => Ok(from_metadata!(m)),
+            RuntimeMetadata::V15(m) => Ok(from_metadata!(m)),
+            m => Err(ClientError::UnsupportedMetadataVersion(m.version())),
+        }
+    }
+
+    fn from_pallets<'a>(
+        types: &PortableRegistry,
+        pallets: impl Iterator<Item = PalletRef<'a>>,
+        signed_extensions: Vec<(String, u32, u32)>,
+    ) -> Self {
+        let mut runtime = RuntimeCalls {
+            encoded_extensions: signed_extensions
+                .iter()
+                .filter(|(_, ty, additional)| {
+                    !is_zero_sized(types, *ty) || !is_zero_sized(types, *additional)
+                })
+                .map(|(name, ..)| name.clone())
+                .collect(),
+            signed_extensions: signed_extensions
+                .into_iter()
+                .map(|(name, ..)| name)
+                .collect(),
+            ..Default::default()
+        };
+        for pallet in pallets {
+            let calls = pallet
+                .calls
+                .and_then(|c| variants(types, c.ty.id))
+                .unwrap_or_default();
+            if pallet.name == "Proxy" {
+                runtime.proxy_types = calls
+                    .iter()
+                    .find(|v| v.name == "add_proxy")
+                    .and_then(|v| {
+                        v.fields
+                            .iter()
+                            .find(|f| f.name.as_deref() == Some("proxy_type"))
+                    })
+                    .and_then(|f| variants(types, f.ty.id))
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|v| (v.name.clone(), v.index))
+                    .collect();
+            }
+            if pallet.name == "Balances" {
+                runtime.existential_deposit = pallet
+                    .constants
+                    .iter()
+                    .find(|c| c.name == "ExistentialDeposit")
+                    .and_then(|c| Balance::decode(&mut c.value.as_slice()).ok());
+            }
+            if VOTER_LIST.contains(&pallet.name) {
+                runtime.bag_thresholds = pallet
+                    .constants
+                    .iter()
+                    .find(|c| c.name == "BagThresholds")
+                    .and_then(|c| Vec::<Score>::decode(&mut c.value.as_slice()).ok())
+                    .unwrap_or_default();
+            }
+            if pallet.name == "Referenda" {
+                runtime.referenda_tracks = pallet
+                    .constants
+                    .iter()
+                    .find(|c| c.name == "Tracks")
+                    .and_then(|c| decode_tracks(types, c.ty.id, &mut c.value.as_slice()))
+                    .unwrap_or_default();
+            }
+            runtime.pallets.push(PalletCalls {
+                name: pallet.name.to_string(),
+                index: pallet.index,
+                calls: calls
+                    .iter()
+                    .map(|v| CallInfo {
+                        name: v.name.clone(),
+                        index: v.index,
+                        args: v.fields.len(),
+                    })
+                    .collect(),
+            });
+        }
+        runtime
+    }
+
+    fn pallet(&self, names: &[&str]) -> Option<&PalletCalls> {
+        self.pallets
+            .iter()
+            .find(|p| names.contains(&p.name.as_str()))
+    }
+}
+
+/// The variants of type `id` if it is an enum
+fn variants(types: &PortableRegistry, id: u32) -> Option<&[Variant<PortableForm>]> {
+    match &types.resolve(id)?.type_def {
+        TypeDef::Variant(v) => Some(&v.variants),
+        _ => None,
+    }
+}
+
+/// The ids and names of a `Referenda::Tracks` value of type `id`, a sequence
+/// of `(TrackId, TrackInfo)`. The other fields of the track info are skipped.
+fn decode_tracks(
+    types: &PortableRegistry,
+    id: u32,
+    input: &mut &[u8],
+) -> Option<Vec<(TrackId, String)>> {
+    let TypeDef::Sequence(seq) = &types.resolve(id)?.type_def else {
+        return None;
+    };
+    let TypeDef::Tuple(tuple) = &types.resolve(seq.type_param.id)?.type_def else {
+        return None;
+    };
+    let [_, info] = tuple.fields.as_slice() else {
+        return None;
+    };
+    let TypeDef::Composite(info) = &types.resolve(info.id)?.type_def else {
+        return None;
+    };
+    let len = Compact::<u32>::decode(input).ok()?.0;
+    let mut tracks = Vec::new();
+    for _ in 0..len {
+        let track = TrackId::decode(input).ok()?;
+        let mut name = None;
+        for field in &info.fields {
+            if field.name.as_deref() == Some("name") {
+                name = Some(decode_name(types, field.ty.id, input)?);
+            } else {
+                skip_value(types, field.ty.id, input)?;
+            }
+        }
+        tracks.push((track, name?));
+    }
+    Some(tracks)
+}
+
+/// A track name, a string or, in newer runtimes, a zero padded byte array
+fn decode_name(types: &PortableRegistry, id: u32, input: &mut &[u8]) -> Option<String> {
+    match &types.resolve(id)?.type_def {
+        TypeDef::Primitive(TypeDefPrimitive::Str) => String::decode(input).ok(),
+        TypeDef::Array(array) => {
+            let bytes = take(input, array.len as usize)?;
+            let end = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+            String::from_utf8(bytes[..end].to_vec()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Whether values of type `id` encode to nothing, e.g. `()` or a struct of
+/// `PhantomData`
+fn is_zero_sized(types: &PortableRegistry, id: u32) -> bool {
+    match types.resolve(id).map(|t| &t.type_def) {
+        Some(TypeDef::Composite(c)) => c.fields.iter().all(|f| is_zero_sized(types, f.ty.id)),
+        Some(TypeDef::Tuple(t)) => t.fields.iter().all(|f| is_zero_sized(types, f.id)),
+        Some(TypeDef::Array(a)) => a.len == 0 || is_zero_sized(types, a.type_param.id),
+        _ => false,
+    }
+}
+
+/// Advances `input` past a value of type `id`
+fn skip_value(types: &PortableRegistry, id: u32, input: &mut &[u8]) -> Option<()> {
+    match &types.resolve(id)?.type_def {
+        TypeDef::Composite(c) => {
+            for field in &c.fields {
+                skip_value(types, field.ty.id, input)?;
+            }
+        }
+        TypeDef::Variant(v) => {
+            let index = take(input, 1)?[0];
+            let variant = v.variants.iter().find(|v| v.index == index)?;
+            for field in &variant.fields {
+                skip_value(types, field.ty.id, input)?;
+            }
+        }
+        TypeDef::Sequence(seq) => {
+            for _ in 0..Compact::<u32>::decode(input).ok()?.0 {
+                skip_value(types, seq.type_param.id, input)?;
+            }
+        }
+        TypeDef::Array(array) => {
+            for _ in 0..array.len {
+                skip_value(types, array.type_param.id, input)?;
+            }
+        }
+        TypeDef::Tuple(tuple) => {
+            for field in &tuple.fields {
+                skip_value(types, field.id, input)?;
+            }
+        }
+        TypeDef::Primitive(p) => {
+            let len = match p {
+                TypeDefPrimitive::Bool | TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+                TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+                TypeDefPrimitive::Char | TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+                TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+                TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+                TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+                TypeDefPrimitive::Str => Compact::<u32>::decode(input).ok()?.0 as usize,
+            };
+            take(input, len)?;
+        }
+        TypeDef::Compact(_) => {
+            Compact::<u128>::decode(input).ok()?;
+        }
+        TypeDef::BitSequence(_) => return None,
+    }
+    Some(())
+}
+
+/// The next `len` bytes of `input`
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if input.len() < len {
+        return None;
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Some(bytes)
+}
+
+/// A difference between a network's constants and the runtime's metadata
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Mismatch {
+    /// None of the pallet's names is in the runtime
+    MissingPallet { pallet: String, index: u8 },
+    /// The pallet is at another index
+    PalletIndex {
+        pallet: String,
+        expected: u8,
+        actual: u8,
+    },
+    /// None of the call's names is in the pallet
+    MissingCall { pallet: String, call: String },
+    /// The call is at another index
+    CallIndex {
+        pallet: String,
+        call: String,
+        expected: u8,
+        actual: u8,
+    },
+    /// The call takes another number of arguments. Argument types are not
+    /// compared.
+    CallArgs {
+        pallet: String,
+        call: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// The network's referenda track is not in the runtime under that id
+    MissingTrack { id: TrackId, name: String },
+    /// The runtime's referenda track is not in the network's table
+    UnknownTrack { id: TrackId, name: String },
+    /// The existential deposit differs
+    ExistentialDeposit { expected: Balance, actual: Balance },
+    /// The signed extensions encoded with an extrinsic or its signed payload
+    /// differ from those `GenericExtra` and `SignedExtra` encode
+    SignedExtensions {
+        expected: Vec<String>,
+        actual: Vec<String>,
+    },
+    /// The proxy type is at another index
+    ProxyTypeIndex {
+        proxy_type: String,
+        expected: u8,
+        actual: u8,
+    },
+    /// The network's proxy type at `index` is not in the runtime
+    MissingProxyType { index: u8 },
+    /// The first voter list bag threshold that differs, `None` past the end
+    /// of a table
+    BagThreshold {
+        index: usize,
+        expected: Option<Score>,
+        actual: Option<Score>,
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::MissingPallet { pallet, index } => {
+                write!(f, "pallet {pallet} (index {index}) not found")
+            }
+            Mismatch::PalletIndex {
+                pallet,
+                expected,
+                actual,
+            } => write!(f, "pallet {pallet}: index {expected}, runtime has {actual}"),
+            Mismatch::MissingCall { pallet, call } => {
+                write!(f, "call {pallet}.{call} not found")
+            }
+            Mismatch::CallIndex {
+                pallet,
+                call,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "call {pallet}.{call}: index {expected}, runtime has {actual}"
+            ),
+            Mismatch::CallArgs {
+                pallet,
+                call,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "call {pallet}.{call}: {expected} arguments, runtime has {actual}"
+            ),
+            Mismatch::MissingTrack { id, name } => {
+                write!(f, "referenda track {id} {name} not found")
+            }
+            Mismatch::UnknownTrack { id, name } => {
+                write!(
+                    f,
+                    "referenda track {id} {name} of the runtime not in the network"
+                )
+            }
+            Mismatch::ExistentialDeposit { expected, actual } => {
+                write!(f, "existential deposit {expected}, runtime has {actual}")
+            }
+            Mismatch::SignedExtensions { expected, actual } => write!(
+                f,
+                "signed extensions {expected:?}, runtime encodes {actual:?}"
+            ),
+            Mismatch::ProxyTypeIndex {
+                proxy_type,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "proxy type {proxy_type}: index {expected}, runtime has {actual}"
+            ),
+            Mismatch::MissingProxyType { index } => {
+                write!(f, "proxy type with index {index} not found")
+            }
+            Mismatch::BagThreshold {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "voter list bag {index}: threshold {expected:?}, runtime has {actual:?}"
+            ),
+        }
+    }
+}
+
+/// Result of checking a network's constants against a runtime
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MetadataReport {
+    pub network: &'static str,
+    pub spec_version: u32,
+    /// Number of calls checked, referenda tracks aside
+    pub checked: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl MetadataReport {
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for MetadataReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} spec version {}: {} calls checked, {} mismatches",
+            self.network,
+            self.spec_version,
+            self.checked,
+            self.mismatches.len()
+        )?;
+        for mismatch in &self.mismatches {
+            write!(f, "\n  {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A call pdotc builds and the constants of its index
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SupportedCall {
+    /// The trait of the network constants, e.g. `HasStaking`
+    pub capability: &'static str,
+    /// Names of the pallet in the runtimes
+    pub pallets: &'static [&'static str],
+    /// Name of the pallet index constant
+    pub pallet_const: &'static str,
+    /// Names of the call, current name first
+    pub calls: &'static [&'static str],
+    /// Name of the call index constant
+    pub call_const: &'static str,
+    /// Number of arguments the call is built with
+    pub args: usize,
+}
+
+const SYSTEM: &[&str] = &["System"];
+const BALANCES: &[&str] = &["Balances"];
+const STAKING: &[&str] = &["Staking"];
+const VOTER_LIST: &[&str] = &["VoterList", "BagsList"];
+const FAST_UNSTAKE: &[&str] = &["FastUnstake"];
+const NOMINATION_POOLS: &[&str] = &["NominationPools"];
+const CONVICTION_VOTING: &[&str] = &["ConvictionVoting"];
+const PROXY: &[&str] = &["Proxy"];
+const VESTING: &[&str] = &["Vesting"];
+const INDICES: &[&str] = &["Indices"];
+const XCM: &[&str] = &["XcmPallet", "PolkadotXcm"];
+const ASSETS: &[&str] = &["Assets"];
+const MULTISIG: &[&str] = &["Multisig"];
+const UTILITY: &[&str] = &["Utility"];
+const PORTFOLIO: &[&str] = &["Portfolio"];
+const SETTLEMENT: &[&str] = &["Settlement"];
+const IDENTITY: &[&str] = &["Identity"];
+
+macro_rules! supported_calls {
+    ($($capability:ident => $calls_fn:ident {
+        $(($pallets:ident, $pallet:ident, [$($call:literal),+], $call_idx:ident, $args:literal),)*
+    })*) => {
+        /// The calls pdotc builds
+        pub const SUPPORTED_CALLS: &[SupportedCall] = &[$($(
+            SupportedCall {
+                capability: stringify!($capability),
+                pallets: $pallets,
+                pallet_const: stringify!($pallet),
+                calls: &[$($call),+],
+                call_const: stringify!($call_idx),
+                args: $args,
+            },
+        )*)*];
+
+        $(
+            #[doc = concat!(
+                "The calls of [`", stringify!($capability), "`] with their indices in the ",
+                "latest runtime of `N`"
+            )]
+            pub fn $calls_fn<N: $capability>() -> Vec<(&'static SupportedCall, CallIndex)> {
+                SUPPORTED_CALLS
+                    .iter()
+                    .filter(|c| c.capability == stringify!($capability))
+                    .zip([$([N::$pallet, N::$call_idx],)*])
+                    .collect()
+            }
+        )*
+    };
+}
+
+#[rustfmt::skip]
+supported_calls! {
+    SubstrateNetwork => base_calls {
+        (SYSTEM, SYSTEM_PALLET_IDX, ["remark"], SYSTEM_REMARK, 1),
+        (SYSTEM, SYSTEM_PALLET_IDX, ["remark_with_event"], SYSTEM_REMARK_WITH_EVENT, 1),
+        (BALANCES, BALANCE_PALLET_IDX, ["transfer_allow_death", "transfer"], BALANCE_TRANSFER, 2),
+        (BALANCES, BALANCE_PALLET_IDX, ["transfer_keep_alive"], BALANCE_TRANSFER_KEEP_ALIVE, 2),
+        (UTILITY, UTILITY_PALLET_IDX, ["batch"], UTILITY_BATCH, 1),
+        (UTILITY, UTILITY_PALLET_IDX, ["batch_all"], UTILITY_BATCH_ALL, 1),
+        (UTILITY, UTILITY_PALLET_IDX, ["force_batch"], UTILITY_FORCE_BATCH, 1),
+    }
+    HasStaking => staking_calls {
+        (STAKING, STAKING_PALLET_IDX, ["bond"], STAKING_BOND, 2),
+        (STAKING, STAKING_PALLET_IDX, ["bond_extra"], STAKING_BOND_EXTRA, 1),
+        (STAKING, STAKING_PALLET_IDX, ["unbond"], STAKING_UNBOND, 1),
+        (STAKING, STAKING_PALLET_IDX, ["withdraw_unbonded"], STAKING_WITHDRAW_UNBONDED, 1),
+        (STAKING, STAKING_PALLET_IDX, ["nominate"], STAKING_NOMINATE, 1),
+        (STAKING, STAKING_PALLET_IDX, ["chill"], STAKING_CHILL, 0),
+        (STAKING, STAKING_PALLET_IDX, ["rebond"], STAKING_REBOND, 1),
+    }
+    HasSubstrateStaking => substrate_staking_calls {
+        (STAKING, STAKING_PALLET_IDX, ["validate"], STAKING_VALIDATE, 1),
+        (STAKING, STAKING_PALLET_IDX, ["set_payee"], STAKING_SET_PAYEE, 1),
+        (STAKING, STAKING_PALLET_IDX, ["set_controller"], STAKING_SET_CONTROLLER, 0),
+        (STAKING, STAKING_PALLET_IDX, ["payout_stakers"], STAKING_PAYOUT_STAKERS, 2),
+        (STAKING, STAKING_PALLET_IDX, ["reap_stash"], STAKING_REAP_STASH, 2),
+        (STAKING, STAKING_PALLET_IDX, ["kick"], STAKING_KICK, 1),
+        (STAKING, STAKING_PALLET_IDX, ["chill_other"], STAKING_CHILL_OTHER, 1),
+        (STAKING, STAKING_PALLET_IDX, ["payout_stakers_by_page"], STAKING_PAYOUT_STAKERS_BY_PAGE, 3),
+    }
+    HasVoterList => voter_list_calls {
+        (VOTER_LIST, VOTER_LIST_PALLET_IDX, ["rebag"], VOTER_LIST_REBAG, 1),
+        (VOTER_LIST, VOTER_LIST_PALLET_IDX, ["put_in_front_of"], VOTER_LIST_PUT_IN_FRONT_OF, 1),
+    }
+    HasFastUnstake => fast_unstake_calls {
+        (FAST_UNSTAKE, FAST_UNSTAKE_PALLET_IDX, ["register_fast_unstake"], FAST_UNSTAKE_REGISTER_FAST_UNSTAKE, 0),
+        (FAST_UNSTAKE, FAST_UNSTAKE_PALLET_IDX, ["deregister"], FAST_UNSTAKE_DEREGISTER, 0),
+    }
+    HasNominationPools => nomination_pools_calls {
+        (NOMINATION_POOLS, NOMINATION_POOLS_PALLET_IDX, ["join"], NOMINATION_POOLS_JOIN, 2),
+        (NOMINATION_POOLS, NOMINATION_POOLS_PALLET_IDX, ["bond_extra"], NOMINATION_POOLS_BOND_EXTRA, 1),
+        (NOMINATION_POOLS, NOMINATION_POOLS_PALLET_IDX, ["claim_payout"], NOMINATION_POOLS_CLAIM_PAYOUT, 0),
+        (NOMINATION_POOLS, NOMINATION_POOLS_PALLET_IDX, ["unbond"], NOMINATION_POOLS_UNBOND, 2),
+        (NOMINATION_POOLS, NOMINATION_POOLS_PALLET_IDX, ["pool_withdraw_unbonded"], NOMINATION_POOLS_POOL_WITHDRAW_UNBONDED, 2),
+        (NOMINATION_POOLS, NOMINATION_POOLS_PALLET_IDX, ["withdraw_unbonded"], NOMINATION_POOLS_WITHDRAW_UNBONDED, 2),
+        (NOMINATION_POOLS, NOMINATION_POOLS_PALLET_IDX, ["set_claim_permission"], NOMINATION_POOLS_SET_CLAIM_PERMISSION, 1),
+    }
+    HasConvictionVoting => conviction_voting_calls {
+        (CONVICTION_VOTING, CONVICTION_VOTING_PALLET_IDX, ["vote"], CONVICTION_VOTING_VOTE, 2),
+        (CONVICTION_VOTING, CONVICTION_VOTING_PALLET_IDX, ["delegate"], CONVICTION_VOTING_DELEGATE, 4),
+        (CONVICTION_VOTING, CONVICTION_VOTING_PALLET_IDX, ["undelegate"], CONVICTION_VOTING_UNDELEGATE, 1),
+        (CONVICTION_VOTING, CONVICTION_VOTING_PALLET_IDX, ["unlock"], CONVICTION_VOTING_UNLOCK, 2),
+        (CONVICTION_VOTING, CONVICTION_VOTING_PALLET_IDX, ["remove_vote"], CONVICTION_VOTING_REMOVE_VOTE, 2),
+    }
+    HasProxy => proxy_calls {
+        (PROXY, PROXY_PALLET_IDX, ["add_proxy"], PROXY_ADD_PROXY, 3),
+        (PROXY, PROXY_PALLET_IDX, ["remove_proxy"], PROXY_REMOVE_PROXY, 3),
+        (PROXY, PROXY_PALLET_IDX, ["remove_proxies"], PROXY_REMOVE_PROXIES, 0),
+    }
+    HasVesting => vesting_calls {
+        (VESTING, VESTING_PALLET_IDX, ["vest"], VESTING_VEST, 0),
+        (VESTING, VESTING_PALLET_IDX, ["vest_other"], VESTING_VEST_OTHER, 1),
+        (VESTING, VESTING_PALLET_IDX, ["vested_transfer"], VESTING_VESTED_TRANSFER, 2),
+        (VESTING, VESTING_PALLET_IDX, ["merge_schedules"], VESTING_MERGE_SCHEDULES, 2),
+    }
+    HasIndices => indices_calls {
+        (INDICES, INDICES_PALLET_IDX, ["claim"], INDICES_CLAIM, 1),
+        (INDICES, INDICES_PALLET_IDX, ["transfer"], INDICES_TRANSFER, 2),
+        (INDICES, INDICES_PALLET_IDX, ["free"], INDICES_FREE, 1),
+    }
+    HasXcm => xcm_calls {
+        (XCM, XCM_PALLET_IDX, ["limited_reserve_transfer_assets"], XCM_LIMITED_RESERVE_TRANSFER_ASSETS, 5),
+        (XCM, XCM_PALLET_IDX, ["limited_teleport_assets"], XCM_LIMITED_TELEPORT_ASSETS, 5),
+    }
+    HasAssets => assets_calls {
+        (ASSETS, ASSETS_PALLET_IDX, ["transfer"], ASSETS_TRANSFER, 3),
+        (ASSETS, ASSETS_PALLET_IDX, ["transfer_keep_alive"], ASSETS_TRANSFER_KEEP_ALIVE, 3),
+    }
+    HasMultisig => multisig_calls {
+        (MULTISIG, MULTISIG_PALLET_IDX, ["as_multi_threshold_1"], MULTISIG_AS_MULTI_THRESHOLD_1, 2),
+        (MULTISIG, MULTISIG_PALLET_IDX, ["as_multi"], MULTISIG_AS_MULTI, 5),
+        (MULTISIG, MULTISIG_PALLET_IDX, ["approve_as_multi"], MULTISIG_APPROVE_AS_MULTI, 5),
+        (MULTISIG, MULTISIG_PALLET_IDX, ["cancel_as_multi"], MULTISIG_CANCEL_AS_MULTI, 4),
+    }
+    HasPolymeshAssets => polymesh_assets_calls {
+        (PORTFOLIO, PORTFOLIO_PALLET_IDX, ["move_portfolio_funds"], PORTFOLIO_MOVE_PORTFOLIO_FUNDS, 3),
+        (SETTLEMENT, SETTLEMENT_PALLET_IDX, ["add_and_affirm_instruction"], SETTLEMENT_ADD_AND_AFFIRM_INSTRUCTION, 7),
+        (SETTLEMENT, SETTLEMENT_PALLET_IDX, ["affirm_instruction"], SETTLEMENT_AFFIRM_INSTRUCTION, 2),
+    }
+    HasIdentity => identity_calls {
+        (IDENTITY, IDENTITY_PALLET_IDX, ["set_identity"], IDENTITY_SET_IDENTITY, 1),
+        (IDENTITY, IDENTITY_PALLET_IDX, ["set_subs"], IDENTITY_SET_SUBS, 1),
+        (IDENTITY, IDENTITY_PALLET_IDX, ["clear_identity"], IDENTITY_CLEAR_IDENTITY, 0),
+        (IDENTITY, IDENTITY_PALLET_IDX, ["request_judgement"], IDENTITY_REQUEST_JUDGEMENT, 2),
+    }
+    HasPolymeshIdentity => polymesh_identity_calls {
+        (IDENTITY, IDENTITY_PALLET_IDX, ["accept_primary_key"], IDENTITY_ACCEPT_PRIMARY_KEY, 2),
+        (IDENTITY, IDENTITY_PALLET_IDX, ["join_identity_as_key"], IDENTITY_JOIN_AS_KEY, 1),
+        (IDENTITY, IDENTITY_PALLET_IDX, ["remove_authorization"], IDENTITY_REMOVE_AUTHORIZATION, 3),
+    }
+}
+
+/// A network whose calls can be checked against a runtime's metadata
+pub trait VerifiableNetwork: SubstrateNetwork {
+    /// The calls the network builds, with their indices in the latest
+    /// runtime
+    fn supported_calls() -> Vec<(&'static SupportedCall, CallIndex)>;
+
+    /// Differences between the network's other constants and `runtime`,
+    /// e.g. its referenda tracks
+    fn check_constants(_runtime: &RuntimeCalls) -> Vec<Mismatch> {
+        Vec::new()
+    }
+}
+
+/// Checks the tracks of `N` by id and name
+fn check_referenda_tracks<N: HasReferenda>(runtime: &RuntimeCalls) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for track in N::REFERENDA_TRACKS {
+        let found = runtime
+            .referenda_tracks
+            .iter()
+            .any(|(id, name)| *id == track.id && name == track.name);
+        if !found {
+            mismatches.push(Mismatch::MissingTrack {
+                id: track.id,
+                name: track.name.to_string(),
+            });
+        }
+    }
+    for (id, name) in &runtime.referenda_tracks {
+        if !N::REFERENDA_TRACKS
+            .iter()
+            .any(|t| t.id == *id && t.name == name)
+        {
+            mismatches.push(Mismatch::UnknownTrack {
+                id: *id,
+                name: name.clone(),
+            });
+        }
+    }
+    mismatches
+}
+
+/// Checks the indices of the proxy types of `N`, matched by name
+fn check_proxy_types<N: HasProxy>(runtime: &RuntimeCalls) -> Vec<Mismatch> {
+    let encoded = |name: &str| N::ProxyTypeType::from_str(name).ok().map(|t| t.encode());
+    let mut mismatches = Vec::new();
+    for (name, index) in &runtime.proxy_types {
+        if let Some([expected]) = encoded(name).as_deref() {
+            if expected != index {
+                mismatches.push(Mismatch::ProxyTypeIndex {
+                    proxy_type: name.clone(),
+                    expected: *expected,
+                    actual: *index,
+                });
+            }
+        }
+    }
+    // proxy types of the network the runtime has under no name
+    for index in 0..=u8::MAX {
+        let known = N::ProxyTypeType::decode(&mut &[index][..]).is_ok();
+        if known
+            && !runtime
+                .proxy_types
+                .iter()
+                .any(|(name, _)| encoded(name).as_deref() == Some(&[index]))
+        {
+            mismatches.push(Mismatch::MissingProxyType { index });
+        }
+    }
+    mismatches
+}
+
+/// Checks the bag thresholds of `N`, reporting the first that differs
+fn check_bag_thresholds<N: HasVoterList>(runtime: &RuntimeCalls) -> Vec<Mismatch> {
+    let expected = N::VOTER_LIST_BAG_THRESHOLDS;
+    let actual = &runtime.bag_thresholds;
+    (0..expected.len().max(actual.len()))
+        .find(|i| expected.get(*i) != actual.get(*i))
+        .map(|index| Mismatch::BagThreshold {
+            index,
+            expected: expected.get(index).copied(),
+            actual: actual.get(index).copied(),
+        })
+        .into_iter()
+        .collect()
+}
+
+macro_rules! verifiable_network {
+    ($($network:ident: $($calls_fn:ident),+ $(| $($check_fn:ident),+)?;)*) => {$(
+        impl VerifiableNetwork for $network {
+            fn supported_calls() -> Vec<(&'static SupportedCall, CallIndex)> {
+                [$($calls_fn::<$network>()),+].concat()
+            }
+            $(
+                fn check_constants(runtime: &RuntimeCalls) -> Vec<Mismatch> {
+                    [$($check_fn::<$network>(runtime)),+].concat()
+                }
+            )?
+        }
+    )*};
+}
+
+verifiable_network! {
+    Polkadot: base_calls, staking_calls, substrate_staking_calls, voter_list_calls,
+        fast_unstake_calls, nomination_pools_calls, conviction_voting_calls, proxy_calls,
+        vesting_calls, indices_calls, xcm_calls, multisig_calls
+        | check_proxy_types, check_referenda_tracks, check_bag_thresholds;
+    Westend: base_calls, staking_calls, substrate_staking_calls, voter_list_calls,
+        fast_unstake_calls, nomination_pools_calls, conviction_voting_calls, proxy_calls,
+        vesting_calls, indices_calls, xcm_calls, multisig_calls
+        | check_proxy_types, check_referenda_tracks, check_bag_thresholds;
+    Kusama: base_calls, staking_calls, substrate_staking_calls, voter_list_calls,
+        fast_unstake_calls, nomination_pools_calls, conviction_voting_calls, proxy_calls,
+        vesting_calls, indices_calls, xcm_calls, multisig_calls
+        | check_proxy_types, check_referenda_tracks, check_bag_thresholds;
+    Polymesh: base_calls, staking_calls, polymesh_identity_calls, polymesh_assets_calls;
+    PeoplePolkadot: base_calls, proxy_calls, xcm_calls, identity_calls, multisig_calls
+        | check_proxy_types;
+    PeopleKusama: base_calls, proxy_calls, xcm_calls, identity_calls, multisig_calls
+        | check_proxy_types;
+    PeopleWestend: base_calls, proxy_calls, xcm_calls, identity_calls, multisig_calls
+        | check_proxy_types;
+    AssetHubPolkadot: base_calls, proxy_calls, xcm_calls, assets_calls, multisig_calls
+        | check_proxy_types;
+    AssetHubKusama: base_calls, proxy_calls, xcm_calls, assets_calls, multisig_calls
+        | check_proxy_types;
+    AssetHubWestend: base_calls, proxy_calls, xcm_calls, assets_calls, multisig_calls
+        | check_proxy_types;
+}
+
+/// The calls `N` builds, with the indices of runtime `spec_version`
+fn expected_calls<N: VerifiableNetwork>(
+    spec_version: u32,
+) -> Result<Vec<(&'static SupportedCall, CallIndex)>> {
+    N::supported_calls()
+        .into_iter()
+        // polymesh has no existential deposit, its keep alive transfer is
+        // the plain transfer
+        .filter(|(call, _)| {
+            call.call_const != "BALANCE_TRANSFER_KEEP_ALIVE"
+                || N::BALANCE_TRANSFER_KEEP_ALIVE != N::BALANCE_TRANSFER
+        })
+        .map(|(call, index)| Ok((call, call_index::<N>(spec_version, index)?)))
+        .collect()
+}
+
+/// The signed extensions `GenericExtra` and `SignedExtra` encode for `N`, in
+/// order. Extensions that encode nothing, e.g. `CheckWeight`, are left out.
+fn encoded_extensions<N: SubstrateNetwork>() -> [&'static str; 6] {
+    let payment = if N::CHARGE_ASSET_TX_PAYMENT {
+        "ChargeAssetTxPayment"
+    } else {
+        "ChargeTransactionPayment"
+    };
+    [
+        "CheckSpecVersion",
+        "CheckTxVersion",
+        "CheckGenesis",
+        "CheckMortality",
+        "CheckNonce",
+        payment,
+    ]
+}
+
+/// Checks the pallet and call indices and the number of call arguments of
+/// `N`, resolved for runtime `spec_version`, against `runtime`. The types of
+/// the arguments are not checked. The existential deposit, the signed
+/// extensions `N` encodes and its proxy types are checked too, as are its
+/// referenda tracks by id and name and its voter list bag thresholds.
+pub fn verify_network<N: VerifiableNetwork>(
+    runtime: &RuntimeCalls,
+    spec_version: u32,
+) -> Result<MetadataReport> {
+    let expected = expected_calls::<N>(spec_version)?;
+    let mut mismatches = Vec::new();
+    let mut checked_pallets = Vec::new();
+    for (call, [pallet_idx, call_idx]) in expected.iter().copied() {
+        let Some(pallet) = runtime.pallet(call.pallets) else {
+            let mismatch = Mismatch::MissingPallet {
+                pallet: call.pallets[0].to_string(),
+                index: pallet_idx,
+            };
+            if !mismatches.contains(&mismatch) {
+                mismatches.push(mismatch);
+            }
+            continue;
+        };
+        if !checked_pallets.contains(&pallet.name) {
+            checked_pallets.push(pallet.name.clone());
+            if pallet.index != pallet_idx {
+                mismatches.push(Mismatch::PalletIndex {
+                    pallet: pallet.name.clone(),
+                    expected: pallet_idx,
+                    actual: pallet.index,
+                });
+            }
+        }
+        let Some(info) = pallet
+            .calls
+            .iter()
+            .find(|c| call.calls.contains(&c.name.as_str()))
+        else {
+            mismatches.push(Mismatch::MissingCall {
+                pallet: pallet.name.clone(),
+                call: call.calls[0].to_string(),
+            });
+            continue;
+        };
+        if info.index != call_idx {
+            mismatches.push(Mismatch::CallIndex {
+                pallet: pallet.name.clone(),
+                call: info.name.clone(),
+                expected: call_idx,
+                actual: info.index,
+            });
+        }
+        if info.args != call.args {
+            mismatches.push(Mismatch::CallArgs {
+                pallet: pallet.name.clone(),
+                call: info.name.clone(),
+                expected: call.args,
+                actual: info.args,
+            });
+        }
+    }
+    if let Some(actual) = runtime.existential_deposit {
+        if actual != N::BALANCE_EXISTENTIAL_DEPOSIT {
+            mismatches.push(Mismatch::ExistentialDeposit {
+                expected: N::BALANCE_EXISTENTIAL_DEPOSIT,
+                actual,
+            });
+        }
+    }
+    let extensions = encoded_extensions::<N>();
+    if runtime.encoded_extensions != extensions {
+        mismatches.push(Mismatch::SignedExtensions {
+            expected: extensions.iter().map(|e| e.to_string()).collect(),
+            actual: runtime.encoded_extensions.clone(),
+        });
+    }
+    mismatches.extend(N::check_constants(runtime));
+    Ok(MetadataReport {
+        network: N::NAME,
+        spec_version,
+        checked: expected.len(),
+        mismatches,
+    })
+}
+
+impl<S, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
+    /// Returns the pallet and call tables of the runtime at `at_block`
+    pub fn runtime_calls(&self, at_block: Option<H256>) -> Result<RuntimeCalls> {
+        let metadata: String = self
+            .client
+            .post(state_get_metadata(at_block))?
+            .into_result()?;
+        RuntimeCalls::decode(&Vec::from_hex(metadata)?)
+    }
+}
+
+impl<S, Client: RpcClient, N: VerifiableNetwork> Api<'_, S, Client, N> {
+    /// Checks the network's constants against the runtime at `at_block`,
+    /// see [`verify_network`]
+    pub fn verify_metadata(&self, at_block: Option<H256>) -> Result<MetadataReport> {
+        let calls = self.runtime_calls(at_block)?;
+        let spec_version = self.runtime_version_at(at_block)?.spec_version;
+        verify_network::<N>(&calls, spec_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pallets::voter_list::bag_thresholds::WESTEND_BAG_THRESHOLDS;
+
+    fn pallet(name: &str, index: u8, calls: &[(&str, u8, usize)]) -> PalletCalls {
+        PalletCalls {
+            name: name.to_string(),
+            index,
+            calls: calls
+                .iter()
+                .map(|(name, index, args)| CallInfo {
+                    name: name.to_string(),
+                    index: *index,
+                    args: *args,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn verify_polkadot() {
+        let runtime = RuntimeCalls {
+            pallets: vec![
+                pallet(
+                    "System",
+                    0,
+                    &[("remark", 0, 1), ("remark_with_event", 7, 1)],
+                ),
+                pallet(
+                    "Balances",
+                    6,
+                    &[
+                        ("transfer_allow_death", 0, 2),
+                        ("transfer_keep_alive", 3, 2),
+                    ],
+                ),
+                pallet("Utility", 26, &[("batch", 0, 1), ("batch_all", 2, 2)]),
+            ],
+            ..Default::default()
+        };
+        let report = verify_network::<Polkadot>(&runtime, 1_000_000).unwrap();
+        assert!(!report.is_ok());
+        let has = |m: Mismatch| report.mismatches.contains(&m);
+        assert!(has(Mismatch::PalletIndex {
+            pallet: "Balances".to_string(),
+            expected: 5,
+            actual: 6,
+        }));
+        assert!(has(Mismatch::MissingCall {
+            pallet: "Utility".to_string(),
+            call: "force_batch".to_string(),
+        }));
+        assert!(has(Mismatch::CallArgs {
+            pallet: "Utility".to_string(),
+            call: "batch_all".to_string(),
+            expected: 1,
+            actual: 2,
+        }));
+        assert!(has(Mismatch::MissingPallet {
+            pallet: "Staking".to_string(),
+            index: 7,
+        }));
+        // reported once per pallet
+        let balances = report
+            .mismatches
+            .iter()
+            .filter(|m| matches!(m, Mismatch::PalletIndex { .. }))
+            .count();
+        assert_eq!(balances, 1);
+        // System and the calls above are fine
+        assert!(!report
+            .mismatches
+            .iter()
+            .any(|m| m.to_string().contains("System")));
+    }
+
+    const WESTEND_ENCODED_EXTENSIONS: [&str; 7] = [
+        "CheckSpecVersion",
+        "CheckTxVersion",
+        "CheckGenesis",
+        "CheckMortality",
+        "CheckNonce",
+        "ChargeTransactionPayment",
+        "CheckMetadataHash",
+    ];
+
+    /// Pallets, calls and constants of the Westend runtime, spec version
+    /// 1_017_001
+    fn westend_runtime() -> RuntimeCalls {
+        RuntimeCalls {
+            pallets: vec![
+                pallet(
+                    "System",
+                    0,
+                    &[("remark", 0, 1), ("remark_with_event", 7, 1)],
+                ),
+                pallet("Babe", 1, &[]),
+                pallet("Timestamp", 2, &[]),
+                pallet(
+                    "Indices",
+                    3,
+                    &[("claim", 0, 1), ("transfer", 1, 2), ("free", 2, 1)],
+                ),
+                pallet(
+                    "Balances",
+                    4,
+                    &[
+                        ("transfer_allow_death", 0, 2),
+                        ("force_transfer", 2, 3),
+                        ("transfer_keep_alive", 3, 2),
+                    ],
+                ),
+                pallet(
+                    "Staking",
+                    6,
+                    &[
+                        ("bond", 0, 2),
+                        ("bond_extra", 1, 1),
+                        ("unbond", 2, 1),
+                        ("withdraw_unbonded", 3, 1),
+                        ("validate", 4, 1),
+                        ("nominate", 5, 1),
+                        ("chill", 6, 0),
+                        ("set_payee", 7, 1),
+                        ("set_controller", 8, 0),
+                        ("payout_stakers", 18, 2),
+                        ("rebond", 19, 1),
+                        ("reap_stash", 20, 2),
+                        ("kick", 21, 1),
+                        ("chill_other", 23, 1),
+                        ("payout_stakers_by_page", 26, 3),
+                    ],
+                ),
+                pallet("Session", 8, &[]),
+                pallet(
+                    "Utility",
+                    16,
+                    &[("batch", 0, 1), ("batch_all", 2, 1), ("force_batch", 4, 1)],
+                ),
+                pallet(
+                    "Vesting",
+                    19,
+                    &[
+                        ("vest", 0, 0),
+                        ("vest_other", 1, 1),
+                        ("vested_transfer", 2, 2),
+                        ("merge_schedules", 4, 2),
+                    ],
+                ),
+                pallet(
+                    "Proxy",
+                    22,
+                    &[
+                        ("add_proxy", 1, 3),
+                        ("remove_proxy", 2, 3),
+                        ("remove_proxies", 3, 0),
+                    ],
+                ),
+                pallet(
+                    "Multisig",
+                    23,
+                    &[
+                        ("as_multi_threshold_1", 0, 2),
+                        ("as_multi", 1, 5),
+                        ("approve_as_multi", 2, 5),
+                        ("cancel_as_multi", 3, 4),
+                    ],
+                ),
+                pallet(
+                    "VoterList",
+                    25,
+                    &[("rebag", 0, 1), ("put_in_front_of", 1, 1)],
+                ),
+                pallet("Preimage", 28, &[]),
+                pallet(
+                    "NominationPools",
+                    29,
+                    &[
+                        ("join", 0, 2),
+                        ("bond_extra", 1, 1),
+                        ("claim_payout", 2, 0),
+                        ("unbond", 3, 2),
+                        ("pool_withdraw_unbonded", 4, 2),
+                        ("withdraw_unbonded", 5, 2),
+                        ("set_claim_permission", 15, 1),
+                    ],
+                ),
+                pallet(
+                    "FastUnstake",
+                    30,
+                    &[("register_fast_unstake", 0, 0), ("deregister", 1, 0)],
+                ),
+                pallet(
+                    "ConvictionVoting",
+                    31,
+                    &[
+                        ("vote", 0, 2),
+                        ("delegate", 1, 4),
+                        ("undelegate", 2, 1),
+                        ("unlock", 3, 2),
+                        ("remove_vote", 4, 2),
+                    ],
+                ),
+                pallet("Referenda", 32, &[]),
+                pallet("DelegatedStaking", 38, &[]),
+                pallet(
+                    "XcmPallet",
+                    99,
+                    &[
+                        ("limited_reserve_transfer_assets", 8, 5),
+                        ("limited_teleport_assets", 9, 5),
+                    ],
+                ),
+            ],
+            referenda_tracks: [
+                (0, "root"),
+                (1, "whitelisted_caller"),
+                (10, "staking_admin"),
+                (11, "treasurer"),
+                (12, "lease_admin"),
+                (13, "fellowship_admin"),
+                (14, "general_admin"),
+                (15, "auction_admin"),
+                (20, "referendum_canceller"),
+                (21, "referendum_killer"),
+                (30, "small_tipper"),
+                (31, "big_tipper"),
+                (32, "small_spender"),
+                (33, "medium_spender"),
+                (34, "big_spender"),
+            ]
+            .into_iter()
+            .map(|(id, name)| (id, name.to_string()))
+            .collect(),
+            proxy_types: [
+                ("Any", 0),
+                ("NonTransfer", 1),
+                ("Staking", 2),
+                ("IdentityJudgement", 4),
+                ("CancelProxy", 5),
+                ("Auction", 6),
+                ("NominationPools", 7),
+            ]
+            .into_iter()
+            .map(|(name, index)| (name.to_string(), index))
+            .collect(),
+            existential_deposit: Some(10_000_000_000),
+            signed_extensions: [
+                "CheckNonZeroSender",
+                "CheckSpecVersion",
+                "CheckTxVersion",
+                "CheckGenesis",
+                "CheckMortality",
+                "CheckNonce",
+                "CheckWeight",
+                "ChargeTransactionPayment",
+                "CheckMetadataHash",
+            ]
+            .map(String::from)
+            .to_vec(),
+            encoded_extensions: WESTEND_ENCODED_EXTENSIONS.map(String::from).to_vec(),
+            bag_thresholds: WESTEND_BAG_THRESHOLDS.to_vec(),
+        }
+    }
+
+    #[test]
+    fn no_placeholder_pallet_indices() {
+        fn check<N: VerifiableNetwork>() {
+            for (call, [pallet, _]) in N::supported_calls() {
+                assert!(
+                    pallet != 0 || call.pallet_const == "SYSTEM_PALLET_IDX",
+                    "{} {} is System's index 0",
+                    N::NAME,
+                    call.pallet_const
+                );
+            }
+        }
+        check::<Polkadot>();
+        check::<Westend>();
+        check::<Kusama>();
+        check::<Polymesh>();
+        check::<PeoplePolkadot>();
+        check::<PeopleKusama>();
+        check::<PeopleWestend>();
+        check::<AssetHubPolkadot>();
+        check::<AssetHubKusama>();
+        check::<AssetHubWestend>();
+    }
+
+    #[test]
+    fn verify_westend() {
+        let report = verify_network::<Westend>(&westend_runtime(), 1_017_001).unwrap();
+        // pdotc does not encode the mode and hash of `CheckMetadataHash`
+        assert_eq!(
+            report.mismatches,
+            vec![Mismatch::SignedExtensions {
+                expected: WESTEND_ENCODED_EXTENSIONS[..6]
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect(),
+                actual: WESTEND_ENCODED_EXTENSIONS.map(String::from).to_vec(),
+            }],
+            "{report}"
+        );
+        assert_eq!(report.checked, Westend::supported_calls().len());
+    }
+
+    /// Mismatches of `runtime` that are not reported for Westend's
+    fn new_westend_mismatches(runtime: &RuntimeCalls) -> Vec<Mismatch> {
+        let known = verify_network::<Westend>(&westend_runtime(), 1_017_001)
+            .unwrap()
+            .mismatches;
+        verify_network::<Westend>(runtime, 1_017_001)
+            .unwrap()
+            .mismatches
+            .into_iter()
+            .filter(|m| !known.contains(m))
+            .collect()
+    }
+
+    #[test]
+    fn verify_constants() {
+        let mut runtime = westend_runtime();
+        runtime.existential_deposit = Some(1_000_000_000_000);
+        runtime
+            .proxy_types
+            .retain(|(name, _)| name != "NonTransfer");
+        runtime.proxy_types[1].1 = 3;
+        runtime.encoded_extensions.truncate(5);
+        runtime
+            .encoded_extensions
+            .push("ChargeAssetTxPayment".to_string());
+        assert_eq!(
+            new_westend_mismatches(&runtime),
+            vec![
+                Mismatch::ExistentialDeposit {
+                    expected: 10_000_000_000,
+                    actual: 1_000_000_000_000,
+                },
+                Mismatch::SignedExtensions {
+                    expected: WESTEND_ENCODED_EXTENSIONS[..6]
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect(),
+                    actual: WESTEND_ENCODED_EXTENSIONS[..5]
+                        .iter()
+                        .chain(&["ChargeAssetTxPayment"])
+                        .map(|e| e.to_string())
+                        .collect(),
+                },
+                Mismatch::ProxyTypeIndex {
+                    proxy_type: "Staking".to_string(),
+                    expected: 2,
+                    actual: 3,
+                },
+                Mismatch::MissingProxyType { index: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_tracks() {
+        // westend has no wish_for_change track
+        let report = verify_network::<Polkadot>(&westend_runtime(), 1_003_000).unwrap();
+        assert!(report.mismatches.contains(&Mismatch::MissingTrack {
+            id: 2,
+            name: "wish_for_change".to_string(),
+        }));
+
+        let mut runtime = westend_runtime();
+        runtime
+            .referenda_tracks
+            .push((2, "wish_for_change".to_string()));
+        assert_eq!(
+            new_westend_mismatches(&runtime),
+            vec![Mismatch::UnknownTrack {
+                id: 2,
+                name: "wish_for_change".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn verify_bag_thresholds() {
+        let mut runtime = westend_runtime();
+        runtime.bag_thresholds[3] += 1;
+        runtime.bag_thresholds.pop();
+        assert_eq!(
+            new_westend_mismatches(&runtime),
+            vec![Mismatch::BagThreshold {
+                index: 3,
+                expected: Some(13_793_905_044),
+                actual: Some(13_793_905_045),
+            }]
+        );
+
+        let report = verify_network::<Kusama>(&westend_runtime(), 1_003_000).unwrap();
+        assert!(report.mismatches.contains(&Mismatch::BagThreshold {
+            index: 0,
+            expected: Some(33_333_333),
+            actual: Some(10_000_000_000),
+        }));
+    }
+
+    #[test]
+    fn decode_referenda_tracks() {
+        use parity_scale_codec::Encode;
+        use scale_info::{meta_type, Registry, TypeInfo};
+
+        #[derive(Encode, TypeInfo)]
+        enum Curve {
+            LinearDecreasing {
+                length: u32,
+                floor: u32,
+                ceil: u32,
+            },
+            Reciprocal {
+                factor: i64,
+                x_offset: i64,
+                y_offset: i64,
+            },
+        }
+
+        #[derive(Encode, TypeInfo)]
+        struct TrackInfo<Name> {
+            name: Name,
+            max_deciding: u32,
+            decision_deposit: Balance,
+            prepare_period: u32,
+            min_approval: Curve,
+            min_support: Curve,
+        }
+
+        fn decode<Name: Encode + TypeInfo + 'static>(names: [Name; 2]) -> Vec<(TrackId, String)> {
+            let mut registry = Registry::new();
+            let ty = registry.register_type(&meta_type::<Vec<(TrackId, TrackInfo<Name>)>>());
+            let types = PortableRegistry::from(registry);
+            let [root, tipper] = names;
+            let value: Vec<(TrackId, TrackInfo<Name>)> = vec![
+                (
+                    0,
+                    TrackInfo {
+                        name: root,
+                        max_deciding: 1,
+                        decision_deposit: 100,
+                        prepare_period: 20,
+                        min_approval: Curve::Reciprocal {
+                            factor: 1,
+                            x_offset: 2,
+                            y_offset: -3,
+                        },
+                        min_support: Curve::LinearDecreasing {
+                            length: 1,
+                            floor: 0,
+                            ceil: 1,
+                        },
+                    },
+                ),
+                (
+                    30,
+                    TrackInfo {
+                        name: tipper,
+                        max_deciding: 200,
+                        decision_deposit: 1,
+                        prepare_period: 10,
+                        min_approval: Curve::LinearDecreasing {
+                            length: 1,
+                            floor: 0,
+                            ceil: 1,
+                        },
+                        min_support: Curve::Reciprocal {
+                            factor: 4,
+                            x_offset: 5,
+                            y_offset: 6,
+                        },
+                    },
+                ),
+            ];
+            let value = value.encode();
+            decode_tracks(&types, ty.id, &mut value.as_slice()).unwrap()
+        }
+
+        let expected = vec![(0, "root".to_string()), (30, "small_tipper".to_string())];
+        assert_eq!(decode(["root", "small_tipper"]), expected);
+        // newer runtimes pad the name into a byte array
+        let padded = |name: &str| {
+            let mut bytes = [0u8; 25];
+            bytes[..name.len()].copy_from_slice(name.as_bytes());
+            bytes
+        };
+        assert_eq!(decode([padded("root"), padded("small_tipper")]), expected);
+    }
+
+    #[test]
+    fn zero_sized_extensions() {
+        use scale_info::{meta_type, Registry, TypeInfo};
+
+        #[derive(TypeInfo)]
+        struct CheckNonZeroSender;
+
+        #[derive(TypeInfo)]
+        struct CheckWeight(core::marker::PhantomData<u32>, ());
+
+        fn zero_sized<T: TypeInfo + 'static>() -> bool {
+            let mut registry = Registry::new();
+            let ty = registry.register_type(&meta_type::<T>());
+            is_zero_sized(&PortableRegistry::from(registry), ty.id)
+        }
+
+        assert!(zero_sized::<()>());
+        assert!(zero_sized::<CheckNonZeroSender>());
+        assert!(zero_sized::<CheckWeight>());
+        assert!(zero_sized::<((), [u8; 0])>());
+        assert!(!zero_sized::<u32>());
+        assert!(!zero_sized::<((), Option<u8>)>());
+        assert!(zero_sized::<[(); 2]>());
+    }
+}
@@ -0,0 +1,373 @@
+//! Generates a network struct with its [`SubstrateNetwork`] and capability
+//! trait impls, and a proxy type enum, from a runtime's metadata
+//!
+//! [`SubstrateNetwork`]: crate::network::SubstrateNetwork
+
+use std::fmt::Write;
+
+use crate::metadata::{RuntimeCalls, SupportedCall, SUPPORTED_CALLS};
+use crate::Balance;
+
+/// Rust source of a struct `network` with `NAME` `name`, its
+/// `SubstrateNetwork` impl, an impl of each capability trait whose pallet is
+/// in `runtime`, and the proxy type enum `{network}ProxyType`.
+///
+/// Required pallets missing from `runtime` are left for the caller to fill
+/// in, so the source does not compile until they are.
+/// Calls missing from a pallet keep the trait's default index. Constants that
+/// are not in the metadata, e.g. the staking durations, keep the trait's
+/// defaults too.
+pub fn generate(runtime: &RuntimeCalls, network: &str, name: &str) -> String {
+    let mut out = String::new();
+    let proxy_type = format!("{network}ProxyType");
+    let has_proxy = !runtime.proxy_types.is_empty();
+
+    writeln!(out, "#[derive(Debug, Copy, Clone)]").unwrap();
+    writeln!(out, "pub struct {network};").unwrap();
+
+    let mut capabilities: Vec<&str> = Vec::new();
+    for call in SUPPORTED_CALLS {
+        if !capabilities.contains(&call.capability) {
+            capabilities.push(call.capability);
+        }
+    }
+    for capability in capabilities {
+        let calls = SUPPORTED_CALLS
+            .iter()
+            .filter(|c| c.capability == capability);
+        let base = capability == "SubstrateNetwork";
+        let Some(consts) = write_calls(runtime, calls, base) else {
+            writeln!(out, "\n// {capability}: not available on {name}").unwrap();
+            continue;
+        };
+        writeln!(out, "\nimpl {capability} for {network} {{").unwrap();
+        if base {
+            writeln!(out, "    const NAME: &'static str = \"{name}\";").unwrap();
+        }
+        out.push_str(&consts);
+        if capability == "HasVoterList" {
+            write_bag_thresholds(&mut out, runtime);
+        }
+        if capability == "HasProxy" {
+            writeln!(out, "    type ProxyDelegateType = GenericAddress;").unwrap();
+            let proxy_type = if has_proxy {
+                proxy_type.as_str()
+            } else {
+                "ProxyType"
+            };
+            writeln!(out, "    type ProxyTypeType = {proxy_type};").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        if capability == "HasConvictionVoting" && runtime.pallet(&["Referenda"]).is_some() {
+            writeln!(out, "\nimpl HasReferenda for {network} {{").unwrap();
+            writeln!(out, "    const REFERENDA_TRACKS: &'static [Track] = &[").unwrap();
+            for (id, track) in &runtime.referenda_tracks {
+                writeln!(out, "        Track {{ id: {id}, name: \"{track}\" }},").unwrap();
+            }
+            writeln!(out, "    ];").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+    }
+
+    if has_proxy {
+        writeln!(out).unwrap();
+        write_proxy_type(&mut out, runtime, &proxy_type, name);
+    }
+    out
+}
+
+/// Pallet index constants capabilities inherit from their supertrait
+const INHERITED_PALLET_CONSTS: &[(&str, &str)] = &[("HasSubstrateStaking", "STAKING_PALLET_IDX")];
+
+/// Calls one of which a runtime needs for the capability, where its other
+/// calls are shared with forks that lack it. Polymesh's staking has
+/// `validate`, `set_payee` and `payout_stakers`, but not the paged payouts.
+const DISTINGUISHING_CALLS: &[(&str, &[&str])] =
+    &[("HasSubstrateStaking", &["payout_stakers_by_page"])];
+
+/// The pallet and call index constants of `calls`. `None` if none of the
+/// calls is in `runtime`, or none of the capability's
+/// [`DISTINGUISHING_CALLS`], unless `base` where every pallet is required.
+fn write_calls<'a>(
+    runtime: &RuntimeCalls,
+    calls: impl Iterator<Item = &'a SupportedCall>,
+    base: bool,
+) -> Option<String> {
+    let mut out = String::new();
+    let mut found = Vec::new();
+    let mut pallet_const = "";
+    let mut capability = "";
+    for call in calls {
+        capability = call.capability;
+        let pallet = runtime.pallet(call.pallets);
+        if call.pallet_const != pallet_const {
+            pallet_const = call.pallet_const;
+            // declared by the supertrait's impl
+            let inherited = INHERITED_PALLET_CONSTS.contains(&(call.capability, pallet_const));
+            if !inherited {
+                match pallet {
+                    Some(p) => writeln!(out, "    const {pallet_const}: u8 = {};", p.index),
+                    None => writeln!(
+                        out,
+                        "    // no {} pallet, set {pallet_const} by hand",
+                        call.pallets[0]
+                    ),
+                }
+                .unwrap();
+            }
+            if pallet_const == "BALANCE_PALLET_IDX" {
+                write_balances(&mut out, runtime);
+            }
+        }
+        let Some(pallet) = pallet else {
+            continue;
+        };
+        match pallet
+            .calls
+            .iter()
+            .find(|c| call.calls.contains(&c.name.as_str()))
+        {
+            Some(c) => {
+                found.push(c.name.as_str());
+                writeln!(out, "    const {}: u8 = {};", call.call_const, c.index)
+            }
+            None => writeln!(out, "    // {} has no {}", pallet.name, call.calls[0]),
+        }
+        .unwrap();
+    }
+    let distinguishing = DISTINGUISHING_CALLS
+        .iter()
+        .find(|(c, _)| *c == capability)
+        .map(|(_, calls)| calls.iter().any(|c| found.contains(c)));
+    (base || distinguishing.unwrap_or(!found.is_empty())).then_some(out)
+}
+
+fn write_balances(out: &mut String, runtime: &RuntimeCalls) {
+    match runtime.existential_deposit {
+        Some(ed) => writeln!(
+            out,
+            "    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = {};",
+            separated(ed)
+        ),
+        None => writeln!(
+            out,
+            "    // no `Balances::ExistentialDeposit` in the metadata\n    \
+             const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 0;"
+        ),
+    }
+    .unwrap();
+    if runtime
+        .signed_extensions
+        .iter()
+        .any(|e| e == "ChargeAssetTxPayment")
+    {
+        writeln!(out, "    const CHARGE_ASSET_TX_PAYMENT: bool = true;").unwrap();
+    }
+}
+
+fn write_bag_thresholds(out: &mut String, runtime: &RuntimeCalls) {
+    if runtime.bag_thresholds.is_empty() {
+        writeln!(
+            out,
+            "    // no `BagThresholds` in the metadata, set VOTER_LIST_BAG_THRESHOLDS by hand"
+        )
+        .unwrap();
+        return;
+    }
+    writeln!(
+        out,
+        "    const VOTER_LIST_BAG_THRESHOLDS: &'static [Score] = &["
+    )
+    .unwrap();
+    for threshold in &runtime.bag_thresholds {
+        writeln!(out, "        {},", separated((*threshold).into())).unwrap();
+    }
+    writeln!(out, "    ];").unwrap();
+}
+
+fn write_proxy_type(out: &mut String, runtime: &RuntimeCalls, proxy_type: &str, name: &str) {
+    writeln!(out, "/// Proxy types of {name}").unwrap();
+    writeln!(
+        out,
+        "#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]"
+    )
+    .unwrap();
+    writeln!(out, "pub enum {proxy_type} {{").unwrap();
+    for (position, (variant, index)) in runtime.proxy_types.iter().enumerate() {
+        if usize::from(*index) != position {
+            writeln!(out, "    #[codec(index = {index})]").unwrap();
+        }
+        writeln!(out, "    {variant},").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+
+    let names: Vec<String> = runtime
+        .proxy_types
+        .iter()
+        .map(|(variant, _)| format!("'{variant}'"))
+        .collect();
+    let expecting = match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    };
+    writeln!(out, "impl FromStr for {proxy_type} {{").unwrap();
+    writeln!(out, "    type Err = &'static str;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    fn from_str(s: &str) -> Result<Self, Self::Err> {{"
+    )
+    .unwrap();
+    writeln!(out, "        let ptype = match s {{").unwrap();
+    for (variant, _) in &runtime.proxy_types {
+        writeln!(out, "            \"{variant}\" => {proxy_type}::{variant},").unwrap();
+    }
+    writeln!(
+        out,
+        "            _ => return Err(\"Invalid {proxy_type}. Expecting {expecting}\"),"
+    )
+    .unwrap();
+    writeln!(out, "        }};").unwrap();
+    writeln!(out, "        Ok(ptype)").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+/// `n` with its digits grouped by underscores, e.g. `10_000_000_000`
+fn separated(n: Balance) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push('_');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::{CallInfo, PalletCalls};
+
+    fn pallet(name: &str, index: u8, calls: &[(&str, u8)]) -> PalletCalls {
+        PalletCalls {
+            name: name.to_string(),
+            index,
+            calls: calls
+                .iter()
+                .map(|(name, index)| CallInfo {
+                    name: name.to_string(),
+                    index: *index,
+                    args: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn separated_digits() {
+        assert_eq!(separated(0), "0");
+        assert_eq!(separated(333), "333");
+        assert_eq!(separated(33_333_333), "33_333_333");
+        assert_eq!(separated(10_000_000_000), "10_000_000_000");
+    }
+
+    #[test]
+    fn generate_network() {
+        let runtime = RuntimeCalls {
+            pallets: vec![
+                pallet("System", 0, &[("remark", 0), ("remark_with_event", 7)]),
+                pallet("Balances", 10, &[("transfer_allow_death", 0)]),
+                pallet("Staking", 7, &[("bond", 0), ("payout_stakers_by_page", 26)]),
+                pallet("Proxy", 42, &[("add_proxy", 1)]),
+                pallet("VoterList", 37, &[("rebag", 0)]),
+            ],
+            proxy_types: vec![("Any".to_string(), 0), ("CancelProxy".to_string(), 6)],
+            existential_deposit: Some(1_000_000_000),
+            signed_extensions: vec!["ChargeAssetTxPayment".to_string()],
+            encoded_extensions: vec!["ChargeAssetTxPayment".to_string()],
+            referenda_tracks: vec![],
+            bag_thresholds: vec![10_000, 11_131, u64::MAX],
+        };
+        let code = generate(&runtime, "Example", "example");
+        for line in [
+            "impl SubstrateNetwork for Example {",
+            "    const NAME: &'static str = \"example\";",
+            "    const BALANCE_PALLET_IDX: u8 = 10;",
+            "    const BALANCE_TRANSFER: u8 = 0;",
+            "    // Balances has no transfer_keep_alive",
+            "    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 1_000_000_000;",
+            "    const CHARGE_ASSET_TX_PAYMENT: bool = true;",
+            "    // no Utility pallet, set UTILITY_PALLET_IDX by hand",
+            "// HasVesting: not available on example",
+            "impl HasStaking for Example {\n    const STAKING_PALLET_IDX: u8 = 7;\n    const STAKING_BOND: u8 = 0;",
+            "impl HasSubstrateStaking for Example {\n    // Staking has no validate",
+            "    const STAKING_PAYOUT_STAKERS_BY_PAGE: u8 = 26;",
+            "impl HasProxy for Example {",
+            "    const PROXY_PALLET_IDX: u8 = 42;",
+            "    const PROXY_ADD_PROXY: u8 = 1;",
+            "    type ProxyTypeType = ExampleProxyType;",
+            "    #[codec(index = 6)]\n    CancelProxy,",
+            "            \"CancelProxy\" => ExampleProxyType::CancelProxy,",
+            "Expecting 'Any' or 'CancelProxy'",
+            "    const VOTER_LIST_BAG_THRESHOLDS: &'static [Score] = &[\n        10_000,\n        \
+             11_131,\n        18_446_744_073_709_551_615,\n    ];",
+        ] {
+            assert!(code.contains(line), "{line} not in\n{code}");
+        }
+        assert_eq!(code.matches("STAKING_PALLET_IDX").count(), 1);
+        assert!(!code.contains("HasReferenda"));
+        assert!(!code.contains("UTILITY_PALLET_IDX: u8"));
+    }
+
+    #[test]
+    fn generate_staking_fork() {
+        let runtime = RuntimeCalls {
+            pallets: vec![pallet(
+                "Staking",
+                17,
+                &[
+                    ("bond", 0),
+                    ("validate", 4),
+                    ("set_payee", 7),
+                    ("payout_stakers", 19),
+                ],
+            )],
+            ..Default::default()
+        };
+        let code = generate(&runtime, "Polymesh", "polymesh");
+        assert!(code.contains("impl HasStaking for Polymesh {"), "{code}");
+        assert!(
+            code.contains("// HasSubstrateStaking: not available on polymesh"),
+            "{code}"
+        );
+        assert!(!code.contains("impl HasSubstrateStaking"));
+    }
+
+    #[test]
+    fn generate_referenda_tracks() {
+        let runtime = RuntimeCalls {
+            pallets: vec![
+                pallet("ConvictionVoting", 20, &[("vote", 0)]),
+                pallet("Referenda", 21, &[]),
+            ],
+            referenda_tracks: vec![(0, "root".to_string()), (30, "small_tipper".to_string())],
+            ..Default::default()
+        };
+        let code = generate(&runtime, "Example", "example");
+        assert!(
+            code.contains(
+                "impl HasReferenda for Example {\n    \
+                 const REFERENDA_TRACKS: &'static [Track] = &[\n        \
+                 Track { id: 0, name: \"root\" },\n        \
+                 Track { id: 30, name: \"small_tipper\" },\n    ];"
+            ),
+            "{code}"
+        );
+    }
+}
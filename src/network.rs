@@ -2,18 +2,56 @@ use std::str::FromStr;
 
 use parity_scale_codec::{Decode, Encode};
 
-use crate::pallets::proxy::{ProxyType, WestendProxyType};
-use crate::GenericAddress;
+use crate::client::{ClientError, Result};
+use crate::pallets::proxy::{AssetHubProxyType, PeopleProxyType, ProxyType, WestendProxyType};
+use crate::pallets::referenda::{Track, KUSAMA_TRACKS, POLKADOT_TRACKS, WESTEND_TRACKS};
+use crate::pallets::voter_list::bag_thresholds::{
+    KUSAMA_BAG_THRESHOLDS, POLKADOT_BAG_THRESHOLDS, WESTEND_BAG_THRESHOLDS,
+};
+use crate::pallets::voter_list::Score;
+use crate::pallets::CallIndex;
+use crate::{Balance, GenericAddress};
 
+/// A network and the pallets every supported runtime has. Other pallets are
+/// only available on networks implementing their capability trait, e.g.
+/// [`HasStaking`], so building a call of a pallet a network does not have
+/// fails to compile.
 pub trait SubstrateNetwork: Clone + Copy + 'static {
     // Network name
     const NAME: &'static str;
 
+    /// Call indices of past and present runtimes. The pallet and call
+    /// constants are the indices of the latest runtime, which are used for
+    /// every spec version if this is empty.
+    const CALL_INDEX_TABLES: &'static [CallIndexTable] = &[];
+
+    // System Pallet
+    /// System is the first pallet of every runtime
+    const SYSTEM_PALLET_IDX: u8 = 0;
+    const SYSTEM_REMARK: u8 = 0;
+    const SYSTEM_REMARK_WITH_EVENT: u8 = 7;
+
     // Balance Pallet
     const BALANCE_PALLET_IDX: u8;
     const BALANCE_TRANSFER: u8 = 0;
+    const BALANCE_TRANSFER_KEEP_ALIVE: u8 = 3;
+    /// Minimum balance an account must hold to exist
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance;
 
-    // Staking Pallet
+    // Transaction Payment
+    /// Fees are charged with `ChargeAssetTxPayment`, which can pay them in an
+    /// asset, instead of `ChargeTransactionPayment`
+    const CHARGE_ASSET_TX_PAYMENT: bool = false;
+
+    // Utility Pallet
+    const UTILITY_PALLET_IDX: u8;
+    const UTILITY_BATCH: u8 = 0;
+    const UTILITY_BATCH_ALL: u8 = 2;
+    const UTILITY_FORCE_BATCH: u8 = 4;
+}
+
+/// A network with the Staking pallet
+pub trait HasStaking: SubstrateNetwork {
     const STAKING_PALLET_IDX: u8;
     const STAKING_BOND: u8 = 0;
     const STAKING_BOND_EXTRA: u8 = 1;
@@ -22,20 +60,214 @@ pub trait SubstrateNetwork: Clone + Copy + 'static {
     const STAKING_NOMINATE: u8 = 5;
     const STAKING_CHILL: u8 = 6;
     const STAKING_REBOND: u8 = 19;
+    /// Number of eras to keep in history, after which rewards can no longer be
+    /// claimed
+    const STAKING_HISTORY_DEPTH: u32 = 84;
+    /// Maximum number of validators a nominator may nominate
+    const STAKING_MAX_NOMINATIONS: u32 = 16;
+    /// Duration of an era in milliseconds
+    const STAKING_ERA_DURATION_MS: u64 = 24 * 60 * 60 * 1000;
+    /// Number of eras funds stay locked after unbonding
+    const STAKING_BONDING_DURATION: u32 = 28;
+}
 
-    // Proxy Pallet
+/// A network with upstream Substrate's Staking pallet. Polymesh's fork of it
+/// only shares the calls of [`HasStaking`].
+pub trait HasSubstrateStaking: HasStaking {
+    const STAKING_VALIDATE: u8 = 4;
+    const STAKING_SET_PAYEE: u8 = 7;
+    const STAKING_SET_CONTROLLER: u8 = 8;
+    const STAKING_PAYOUT_STAKERS: u8 = 18;
+    const STAKING_REAP_STASH: u8 = 20;
+    const STAKING_KICK: u8 = 21;
+    const STAKING_CHILL_OTHER: u8 = 23;
+    const STAKING_PAYOUT_STAKERS_BY_PAGE: u8 = 26;
+}
+
+/// A network with the Bags List pallet (VoterList) ranking its stakers
+pub trait HasVoterList: HasStaking {
+    const VOTER_LIST_PALLET_IDX: u8;
+    /// Upper thresholds of the bags, the runtime's `BagThresholds`
+    const VOTER_LIST_BAG_THRESHOLDS: &'static [Score];
+    const VOTER_LIST_REBAG: u8 = 0;
+    const VOTER_LIST_PUT_IN_FRONT_OF: u8 = 1;
+}
+
+/// A network with the Fast Unstake pallet
+pub trait HasFastUnstake: HasStaking {
+    const FAST_UNSTAKE_PALLET_IDX: u8;
+    const FAST_UNSTAKE_REGISTER_FAST_UNSTAKE: u8 = 0;
+    const FAST_UNSTAKE_DEREGISTER: u8 = 1;
+}
+
+/// A network with the Nomination Pools pallet
+pub trait HasNominationPools: SubstrateNetwork {
+    const NOMINATION_POOLS_PALLET_IDX: u8;
+    const NOMINATION_POOLS_JOIN: u8 = 0;
+    const NOMINATION_POOLS_BOND_EXTRA: u8 = 1;
+    const NOMINATION_POOLS_CLAIM_PAYOUT: u8 = 2;
+    const NOMINATION_POOLS_UNBOND: u8 = 3;
+    const NOMINATION_POOLS_POOL_WITHDRAW_UNBONDED: u8 = 4;
+    const NOMINATION_POOLS_WITHDRAW_UNBONDED: u8 = 5;
+    const NOMINATION_POOLS_SET_CLAIM_PERMISSION: u8 = 15;
+}
+
+/// A network with the Conviction Voting pallet
+pub trait HasConvictionVoting: SubstrateNetwork {
+    const CONVICTION_VOTING_PALLET_IDX: u8;
+    const CONVICTION_VOTING_VOTE: u8 = 0;
+    const CONVICTION_VOTING_DELEGATE: u8 = 1;
+    const CONVICTION_VOTING_UNDELEGATE: u8 = 2;
+    const CONVICTION_VOTING_UNLOCK: u8 = 3;
+    const CONVICTION_VOTING_REMOVE_VOTE: u8 = 4;
+}
+
+/// A network with the Referenda pallet
+pub trait HasReferenda: SubstrateNetwork {
+    /// Tracks referenda are submitted on
+    const REFERENDA_TRACKS: &'static [Track];
+}
+
+/// A network with the Proxy pallet
+pub trait HasProxy: SubstrateNetwork {
     const PROXY_PALLET_IDX: u8;
     const PROXY_ADD_PROXY: u8 = 1;
     const PROXY_REMOVE_PROXY: u8 = 2;
     const PROXY_REMOVE_PROXIES: u8 = 3;
     type ProxyDelegateType: Encode + Decode + Clone + FromStr<Err = &'static str>;
     type ProxyTypeType: Encode + Decode + Clone + FromStr<Err = &'static str>;
+}
 
-    // Identity Pallet
-    const IDENTITY_PALLET_IDX: u8 = 7;
+/// A network with the Vesting pallet
+pub trait HasVesting: SubstrateNetwork {
+    const VESTING_PALLET_IDX: u8;
+    const VESTING_VEST: u8 = 0;
+    const VESTING_VEST_OTHER: u8 = 1;
+    const VESTING_VESTED_TRANSFER: u8 = 2;
+    const VESTING_MERGE_SCHEDULES: u8 = 4;
+}
+
+/// A network with the Indices pallet
+pub trait HasIndices: SubstrateNetwork {
+    const INDICES_PALLET_IDX: u8;
+    const INDICES_CLAIM: u8 = 0;
+    const INDICES_TRANSFER: u8 = 1;
+    const INDICES_FREE: u8 = 2;
+}
+
+/// A network with the XCM pallet, `XcmPallet` on the relay chains and
+/// `PolkadotXcm` on parachains
+pub trait HasXcm: SubstrateNetwork {
+    const XCM_PALLET_IDX: u8;
+    const XCM_LIMITED_RESERVE_TRANSFER_ASSETS: u8 = 8;
+    const XCM_LIMITED_TELEPORT_ASSETS: u8 = 9;
+}
+
+/// A network with the Assets pallet
+pub trait HasAssets: SubstrateNetwork {
+    const ASSETS_PALLET_IDX: u8;
+    const ASSETS_TRANSFER: u8 = 8;
+    const ASSETS_TRANSFER_KEEP_ALIVE: u8 = 9;
+}
+
+/// A network with the Multisig pallet
+pub trait HasMultisig: SubstrateNetwork {
+    const MULTISIG_PALLET_IDX: u8;
+    const MULTISIG_AS_MULTI_THRESHOLD_1: u8 = 0;
+    const MULTISIG_AS_MULTI: u8 = 1;
+    const MULTISIG_APPROVE_AS_MULTI: u8 = 2;
+    const MULTISIG_CANCEL_AS_MULTI: u8 = 3;
+}
+
+/// A network with the Identity pallet
+pub trait HasIdentity: SubstrateNetwork {
+    const IDENTITY_PALLET_IDX: u8;
+    const IDENTITY_SET_IDENTITY: u8 = 1;
+    const IDENTITY_SET_SUBS: u8 = 2;
+    const IDENTITY_CLEAR_IDENTITY: u8 = 3;
+    const IDENTITY_REQUEST_JUDGEMENT: u8 = 4;
+}
+
+/// A network with Polymesh's Identity pallet
+pub trait HasPolymeshIdentity: SubstrateNetwork {
+    const IDENTITY_PALLET_IDX: u8;
+    const IDENTITY_ACCEPT_PRIMARY_KEY: u8 = 3;
+    /// `join_identity_as_key`
     const IDENTITY_JOIN_AS_KEY: u8 = 5;
+    const IDENTITY_REMOVE_AUTHORIZATION: u8 = 14;
 }
 
+/// A network with Polymesh's Portfolio and Settlement pallets
+pub trait HasPolymeshAssets: SubstrateNetwork {
+    // Polymesh Portfolio Pallet
+    const PORTFOLIO_PALLET_IDX: u8;
+    const PORTFOLIO_MOVE_PORTFOLIO_FUNDS: u8 = 2;
+
+    // Polymesh Settlement Pallet
+    const SETTLEMENT_PALLET_IDX: u8;
+    const SETTLEMENT_ADD_AND_AFFIRM_INSTRUCTION: u8 = 10;
+    const SETTLEMENT_AFFIRM_INSTRUCTION: u8 = 11;
+}
+
+/// The call indices of a range of runtime spec versions
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct CallIndexTable {
+    /// First spec version of the range
+    pub from_spec_version: u32,
+    /// Last spec version of the range, `None` for the latest runtime
+    pub to_spec_version: Option<u32>,
+    /// Call indices of the latest runtime and what they are in this range,
+    /// for the calls that differ
+    pub call_indices: &'static [(CallIndex, CallIndex)],
+}
+
+impl CallIndexTable {
+    pub fn contains(&self, spec_version: u32) -> bool {
+        spec_version >= self.from_spec_version
+            && self.to_spec_version.is_none_or(|to| spec_version <= to)
+    }
+
+    /// The index in this range of `index` of the latest runtime
+    pub fn call_index(&self, index: CallIndex) -> CallIndex {
+        self.call_indices
+            .iter()
+            .find_map(|(latest, this)| (*latest == index).then_some(*this))
+            .unwrap_or(index)
+    }
+}
+
+/// The call index table of `N` covering runtime `spec_version`, `None` if
+/// `N` has no tables. Errors if `N` has tables, but none of them covers
+/// `spec_version`.
+pub fn call_index_table<N: SubstrateNetwork>(
+    spec_version: u32,
+) -> Result<Option<&'static CallIndexTable>> {
+    if N::CALL_INDEX_TABLES.is_empty() {
+        return Ok(None);
+    }
+    N::CALL_INDEX_TABLES
+        .iter()
+        .find(|t| t.contains(spec_version))
+        .map(Some)
+        .ok_or(ClientError::UnknownSpecVersion(spec_version))
+}
+
+/// Looks up the index of `index` of the latest runtime for runtime
+/// `spec_version` of network `N`, see [`call_index_table`]
+pub fn call_index<N: SubstrateNetwork>(spec_version: u32, index: CallIndex) -> Result<CallIndex> {
+    Ok(call_index_table::<N>(spec_version)?
+        .map(|t| t.call_index(index))
+        .unwrap_or(index))
+}
+
+/// The indices of the 1.x relay chain runtimes. Later spec versions are
+/// rejected until their indices are checked against their metadata.
+const RELAY_CALL_INDEX_TABLES: &[CallIndexTable] = &[CallIndexTable {
+    from_spec_version: 1_000_000,
+    to_spec_version: Some(1_999_999),
+    call_indices: &[],
+}];
+
 #[derive(Debug, Copy, Clone)]
 pub struct Polkadot;
 #[derive(Debug, Copy, Clone)]
@@ -44,39 +276,398 @@ pub struct Westend;
 pub struct Kusama;
 #[derive(Debug, Copy, Clone)]
 pub struct Polymesh;
+#[derive(Debug, Copy, Clone)]
+pub struct PeoplePolkadot;
+#[derive(Debug, Copy, Clone)]
+pub struct PeopleKusama;
+#[derive(Debug, Copy, Clone)]
+pub struct PeopleWestend;
+#[derive(Debug, Copy, Clone)]
+pub struct AssetHubPolkadot;
+#[derive(Debug, Copy, Clone)]
+pub struct AssetHubKusama;
+#[derive(Debug, Copy, Clone)]
+pub struct AssetHubWestend;
 
 impl SubstrateNetwork for Polkadot {
     const NAME: &'static str = "polkadot";
+    const CALL_INDEX_TABLES: &'static [CallIndexTable] = RELAY_CALL_INDEX_TABLES;
     const BALANCE_PALLET_IDX: u8 = 5;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 10_000_000_000;
+    const UTILITY_PALLET_IDX: u8 = 26;
+}
+
+impl HasStaking for Polkadot {
     const STAKING_PALLET_IDX: u8 = 7;
+}
+
+impl HasSubstrateStaking for Polkadot {}
+
+impl HasVoterList for Polkadot {
+    const VOTER_LIST_PALLET_IDX: u8 = 37;
+    const VOTER_LIST_BAG_THRESHOLDS: &'static [Score] = &POLKADOT_BAG_THRESHOLDS;
+}
+
+impl HasFastUnstake for Polkadot {
+    const FAST_UNSTAKE_PALLET_IDX: u8 = 40;
+}
+
+impl HasNominationPools for Polkadot {
+    const NOMINATION_POOLS_PALLET_IDX: u8 = 39;
+}
+
+impl HasConvictionVoting for Polkadot {
+    const CONVICTION_VOTING_PALLET_IDX: u8 = 20;
+}
+
+impl HasReferenda for Polkadot {
+    const REFERENDA_TRACKS: &'static [Track] = POLKADOT_TRACKS;
+}
+
+impl HasProxy for Polkadot {
     const PROXY_PALLET_IDX: u8 = 29;
     type ProxyDelegateType = GenericAddress;
     type ProxyTypeType = ProxyType;
 }
 
+impl HasVesting for Polkadot {
+    const VESTING_PALLET_IDX: u8 = 25;
+}
+
+impl HasIndices for Polkadot {
+    const INDICES_PALLET_IDX: u8 = 4;
+}
+
+impl HasXcm for Polkadot {
+    const XCM_PALLET_IDX: u8 = 99;
+}
+
+impl HasMultisig for Polkadot {
+    const MULTISIG_PALLET_IDX: u8 = 30;
+}
+
 impl SubstrateNetwork for Westend {
     const NAME: &'static str = "westend";
     const BALANCE_PALLET_IDX: u8 = 4;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 10_000_000_000;
+    const UTILITY_PALLET_IDX: u8 = 16;
+}
+
+impl HasStaking for Westend {
     const STAKING_PALLET_IDX: u8 = 6;
+    const STAKING_ERA_DURATION_MS: u64 = 6 * 60 * 60 * 1000;
+    const STAKING_BONDING_DURATION: u32 = 2;
+}
+
+impl HasSubstrateStaking for Westend {}
+
+impl HasVoterList for Westend {
+    const VOTER_LIST_PALLET_IDX: u8 = 25;
+    const VOTER_LIST_BAG_THRESHOLDS: &'static [Score] = &WESTEND_BAG_THRESHOLDS;
+}
+
+impl HasFastUnstake for Westend {
+    const FAST_UNSTAKE_PALLET_IDX: u8 = 30;
+}
+
+impl HasNominationPools for Westend {
+    const NOMINATION_POOLS_PALLET_IDX: u8 = 29;
+}
+
+impl HasConvictionVoting for Westend {
+    const CONVICTION_VOTING_PALLET_IDX: u8 = 31;
+}
+
+impl HasReferenda for Westend {
+    const REFERENDA_TRACKS: &'static [Track] = WESTEND_TRACKS;
+}
+
+impl HasProxy for Westend {
     const PROXY_PALLET_IDX: u8 = 22;
     type ProxyDelegateType = GenericAddress;
     type ProxyTypeType = WestendProxyType;
 }
 
+impl HasVesting for Westend {
+    const VESTING_PALLET_IDX: u8 = 19;
+}
+
+impl HasIndices for Westend {
+    const INDICES_PALLET_IDX: u8 = 3;
+}
+
+impl HasXcm for Westend {
+    const XCM_PALLET_IDX: u8 = 99;
+}
+
+impl HasMultisig for Westend {
+    const MULTISIG_PALLET_IDX: u8 = 23;
+}
+
 impl SubstrateNetwork for Kusama {
     const NAME: &'static str = "kusama";
+    const CALL_INDEX_TABLES: &'static [CallIndexTable] = RELAY_CALL_INDEX_TABLES;
     const BALANCE_PALLET_IDX: u8 = 4;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 333_333_333;
+    const UTILITY_PALLET_IDX: u8 = 24;
+}
+
+impl HasStaking for Kusama {
     const STAKING_PALLET_IDX: u8 = 6;
+    const STAKING_MAX_NOMINATIONS: u32 = 24;
+    const STAKING_ERA_DURATION_MS: u64 = 6 * 60 * 60 * 1000;
+}
+
+impl HasSubstrateStaking for Kusama {}
+
+impl HasVoterList for Kusama {
+    const VOTER_LIST_PALLET_IDX: u8 = 39;
+    const VOTER_LIST_BAG_THRESHOLDS: &'static [Score] = &KUSAMA_BAG_THRESHOLDS;
+}
+
+impl HasFastUnstake for Kusama {
+    const FAST_UNSTAKE_PALLET_IDX: u8 = 42;
+}
+
+impl HasNominationPools for Kusama {
+    const NOMINATION_POOLS_PALLET_IDX: u8 = 41;
+}
+
+impl HasConvictionVoting for Kusama {
+    const CONVICTION_VOTING_PALLET_IDX: u8 = 20;
+}
+
+impl HasReferenda for Kusama {
+    const REFERENDA_TRACKS: &'static [Track] = KUSAMA_TRACKS;
+}
+
+impl HasProxy for Kusama {
     const PROXY_PALLET_IDX: u8 = 30;
     type ProxyDelegateType = GenericAddress;
     type ProxyTypeType = ProxyType;
 }
 
+impl HasVesting for Kusama {
+    const VESTING_PALLET_IDX: u8 = 28;
+}
+
+impl HasIndices for Kusama {
+    const INDICES_PALLET_IDX: u8 = 3;
+}
+
+impl HasXcm for Kusama {
+    const XCM_PALLET_IDX: u8 = 99;
+}
+
+impl HasMultisig for Kusama {
+    const MULTISIG_PALLET_IDX: u8 = 31;
+}
+
 impl SubstrateNetwork for Polymesh {
     const NAME: &'static str = "polymesh";
     const BALANCE_PALLET_IDX: u8 = 5;
+    // polymesh has no existential deposit, so a plain transfer keeps the
+    // account alive
+    const BALANCE_TRANSFER_KEEP_ALIVE: u8 = 0;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 0;
+    const UTILITY_PALLET_IDX: u8 = 41;
+}
+
+impl HasStaking for Polymesh {
     const STAKING_PALLET_IDX: u8 = 10;
-    const PROXY_PALLET_IDX: u8 = 0;
+}
+
+impl HasPolymeshIdentity for Polymesh {
+    const IDENTITY_PALLET_IDX: u8 = 7;
+}
+
+impl HasPolymeshAssets for Polymesh {
+    const PORTFOLIO_PALLET_IDX: u8 = 34;
+    const SETTLEMENT_PALLET_IDX: u8 = 37;
+}
+
+impl SubstrateNetwork for PeoplePolkadot {
+    const NAME: &'static str = "people-polkadot";
+    const BALANCE_PALLET_IDX: u8 = 10;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 1_000_000_000;
+    const UTILITY_PALLET_IDX: u8 = 40;
+}
+
+impl HasProxy for PeoplePolkadot {
+    const PROXY_PALLET_IDX: u8 = 42;
     type ProxyDelegateType = GenericAddress;
-    type ProxyTypeType = ProxyType;
+    type ProxyTypeType = PeopleProxyType;
+}
+
+impl HasXcm for PeoplePolkadot {
+    const XCM_PALLET_IDX: u8 = 31;
+}
+
+impl HasIdentity for PeoplePolkadot {
+    const IDENTITY_PALLET_IDX: u8 = 50;
+}
+
+impl HasMultisig for PeoplePolkadot {
+    const MULTISIG_PALLET_IDX: u8 = 41;
+}
+
+impl SubstrateNetwork for PeopleKusama {
+    const NAME: &'static str = "people-kusama";
+    const BALANCE_PALLET_IDX: u8 = 10;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 33_333_333;
+    const UTILITY_PALLET_IDX: u8 = 40;
+}
+
+impl HasProxy for PeopleKusama {
+    const PROXY_PALLET_IDX: u8 = 42;
+    type ProxyDelegateType = GenericAddress;
+    type ProxyTypeType = PeopleProxyType;
+}
+
+impl HasXcm for PeopleKusama {
+    const XCM_PALLET_IDX: u8 = 31;
+}
+
+impl HasIdentity for PeopleKusama {
+    const IDENTITY_PALLET_IDX: u8 = 50;
+}
+
+impl HasMultisig for PeopleKusama {
+    const MULTISIG_PALLET_IDX: u8 = 41;
+}
+
+impl SubstrateNetwork for PeopleWestend {
+    const NAME: &'static str = "people-westend";
+    const BALANCE_PALLET_IDX: u8 = 10;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 1_000_000_000;
+    const UTILITY_PALLET_IDX: u8 = 40;
+}
+
+impl HasProxy for PeopleWestend {
+    const PROXY_PALLET_IDX: u8 = 42;
+    type ProxyDelegateType = GenericAddress;
+    type ProxyTypeType = PeopleProxyType;
+}
+
+impl HasXcm for PeopleWestend {
+    const XCM_PALLET_IDX: u8 = 31;
+}
+
+impl HasIdentity for PeopleWestend {
+    const IDENTITY_PALLET_IDX: u8 = 50;
+}
+
+impl HasMultisig for PeopleWestend {
+    const MULTISIG_PALLET_IDX: u8 = 41;
+}
+
+impl SubstrateNetwork for AssetHubPolkadot {
+    const NAME: &'static str = "asset-hub-polkadot";
+    const BALANCE_PALLET_IDX: u8 = 10;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 1_000_000_000;
+    const CHARGE_ASSET_TX_PAYMENT: bool = true;
+    const UTILITY_PALLET_IDX: u8 = 40;
+}
+
+impl HasProxy for AssetHubPolkadot {
+    const PROXY_PALLET_IDX: u8 = 42;
+    type ProxyDelegateType = GenericAddress;
+    type ProxyTypeType = AssetHubProxyType;
+}
+
+impl HasXcm for AssetHubPolkadot {
+    const XCM_PALLET_IDX: u8 = 31;
+}
+
+impl HasAssets for AssetHubPolkadot {
+    const ASSETS_PALLET_IDX: u8 = 50;
+}
+
+impl HasMultisig for AssetHubPolkadot {
+    const MULTISIG_PALLET_IDX: u8 = 41;
+}
+
+impl SubstrateNetwork for AssetHubKusama {
+    const NAME: &'static str = "asset-hub-kusama";
+    const BALANCE_PALLET_IDX: u8 = 10;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 33_333_333;
+    const CHARGE_ASSET_TX_PAYMENT: bool = true;
+    const UTILITY_PALLET_IDX: u8 = 40;
+}
+
+impl HasProxy for AssetHubKusama {
+    const PROXY_PALLET_IDX: u8 = 42;
+    type ProxyDelegateType = GenericAddress;
+    type ProxyTypeType = AssetHubProxyType;
+}
+
+impl HasXcm for AssetHubKusama {
+    const XCM_PALLET_IDX: u8 = 31;
+}
+
+impl HasAssets for AssetHubKusama {
+    const ASSETS_PALLET_IDX: u8 = 50;
+}
+
+impl HasMultisig for AssetHubKusama {
+    const MULTISIG_PALLET_IDX: u8 = 41;
+}
+
+impl SubstrateNetwork for AssetHubWestend {
+    const NAME: &'static str = "asset-hub-westend";
+    const BALANCE_PALLET_IDX: u8 = 10;
+    const BALANCE_EXISTENTIAL_DEPOSIT: Balance = 1_000_000_000;
+    const CHARGE_ASSET_TX_PAYMENT: bool = true;
+    const UTILITY_PALLET_IDX: u8 = 40;
+}
+
+impl HasProxy for AssetHubWestend {
+    const PROXY_PALLET_IDX: u8 = 42;
+    type ProxyDelegateType = GenericAddress;
+    type ProxyTypeType = AssetHubProxyType;
+}
+
+impl HasXcm for AssetHubWestend {
+    const XCM_PALLET_IDX: u8 = 31;
+}
+
+impl HasAssets for AssetHubWestend {
+    const ASSETS_PALLET_IDX: u8 = 50;
+}
+
+impl HasMultisig for AssetHubWestend {
+    const MULTISIG_PALLET_IDX: u8 = 41;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_index_table() {
+        let old = CallIndexTable {
+            from_spec_version: 100,
+            to_spec_version: Some(199),
+            call_indices: &[([5, 3], [5, 2])],
+        };
+        assert!(old.contains(100) && old.contains(199));
+        assert!(!old.contains(99) && !old.contains(200));
+        assert_eq!(old.call_index([5, 3]), [5, 2]);
+        assert_eq!(old.call_index([5, 0]), [5, 0]);
+
+        let latest = CallIndexTable {
+            from_spec_version: 200,
+            to_spec_version: None,
+            call_indices: &[],
+        };
+        assert!(latest.contains(u32::MAX));
+
+        // networks without tables use their constants for every version
+        assert_eq!(call_index::<Westend>(0, [4, 3]).unwrap(), [4, 3]);
+        assert_eq!(call_index::<Polkadot>(1_002_000, [5, 3]).unwrap(), [5, 3]);
+        assert!(matches!(
+            call_index::<Polkadot>(2_000_000, [5, 3]),
+            Err(ClientError::UnknownSpecVersion(2_000_000))
+        ));
+    }
 }
@@ -2,19 +2,45 @@ use parity_scale_codec::Encode;
 use sp_core::crypto::AccountId32;
 
 use crate::client::{Api, ClientError, Result, Signer};
-use crate::network::SubstrateNetwork;
+use crate::network::{call_index, call_index_table, SubstrateNetwork};
 use crate::rpc::RpcClient;
 use crate::{Era, GenericExtra, SignedPayload, UncheckedExtrinsic};
 
+pub mod assets;
 pub mod balances;
+pub mod conviction_voting;
+pub mod fast_unstake;
 pub mod identity;
+pub mod indices;
+pub mod multisig;
+pub mod nomination_pools;
+pub mod polymesh_assets;
+pub mod polymesh_identity;
 pub mod proxy;
+pub mod referenda;
 pub mod staking;
+pub mod staking_payouts;
+pub mod staking_rewards;
 pub mod storage;
+pub mod system;
 pub mod timestamp;
+pub mod utility;
+pub mod validator_selection;
+pub mod vesting;
+pub mod voter_list;
+pub mod xcm_pallet;
 
 pub(crate) type CallIndex = [u8; 2];
 
+impl<S, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
+    /// The index of call `index` of the latest runtime in the runtime the
+    /// extrinsics are created for, see [`SubstrateNetwork::CALL_INDEX_TABLES`].
+    /// Calls passed to [`Api::create_xt`] should be built with it.
+    pub fn call_index(&self, index: CallIndex) -> Result<CallIndex> {
+        call_index::<N>(self.runtime_version.spec_version, index)
+    }
+}
+
 impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
     /// Creates and signs an extrinsic that can be submitted to a node
     pub fn create_xt<C: Encode + Clone>(&self, call: C) -> Result<UncheckedExtrinsic<C>> {
@@ -31,12 +57,14 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         self._create_xt(call, Some(nonce))
     }
 
-    /// Creates and signs an extrinsic that can be submitted to a node
+    /// Creates and signs an extrinsic that can be submitted to a node.
+    /// Errors if the network does not know the runtime's call indices.
     pub(crate) fn _create_xt<C: Encode + Clone>(
         &self,
         call: C,
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<C>> {
+        call_index_table::<N>(self.runtime_version.spec_version)?;
         let signature = if let Some(signer) = &self.signer {
             let gen_hash = self.genesis_hash;
             let runtime_version = self.runtime_version;
@@ -45,7 +73,11 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
             } else {
                 self.nonce()?
             };
-            let extra = GenericExtra::new(Era::Immortal, nonce);
+            let extra = if N::CHARGE_ASSET_TX_PAYMENT {
+                GenericExtra::with_asset_payment(Era::Immortal, nonce, self.fee_asset.clone())
+            } else {
+                GenericExtra::new(Era::Immortal, nonce)
+            };
             let s_extra = (
                 runtime_version.spec_version,
                 runtime_version.transaction_version,
@@ -55,7 +87,7 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
                 (),
                 (),
             );
-            let raw_payload = SignedPayload::new(call.clone(), extra, s_extra);
+            let raw_payload = SignedPayload::new(call.clone(), extra.clone(), s_extra);
             let from = signer.public()?.into();
             let sig = raw_payload.encoded(|payload| signer.sign(payload))?;
             Some((from, sig, extra))
@@ -0,0 +1,257 @@
+use parity_scale_codec::{Compact, Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasAssets;
+use crate::pallets::storage::{storage_assets_account, storage_assets_asset};
+use crate::pallets::xcm_pallet::{Junction, Location};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, GenericAddress, UncheckedExtrinsic, H256};
+
+pub type AssetId = u32;
+
+pub type ComposedAssetsTransfer = (
+    CallIndex,
+    Compact<AssetId>,
+    GenericAddress,
+    Compact<Balance>,
+);
+
+/// Tether USD on Polkadot and Kusama Asset Hub
+pub const USDT_ASSET_ID: AssetId = 1984;
+/// USD Coin on Polkadot Asset Hub
+pub const USDC_ASSET_ID: AssetId = 1337;
+
+/// The location of asset `id` of the network's `Assets` pallet, as used to
+/// pay fees in it
+pub fn asset_location<N: HasAssets>(id: AssetId) -> Location {
+    Location::new(
+        0,
+        vec![
+            Junction::PalletInstance(N::ASSETS_PALLET_IDX),
+            Junction::GeneralIndex(id.into()),
+        ],
+    )
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum AssetStatus {
+    /// The asset is active and able to be used.
+    Live,
+    /// Whether the asset is frozen for non-admin transfers.
+    Frozen,
+    /// The asset is currently being destroyed, and all actions are no longer
+    /// permitted on the asset.
+    Destroying,
+}
+
+/// Details of an asset
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/assets/src/types.rs
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct AssetDetails {
+    /// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
+    pub owner: AccountId32,
+    /// Can mint tokens.
+    pub issuer: AccountId32,
+    /// Can thaw tokens, force transfers and burn tokens from any account.
+    pub admin: AccountId32,
+    /// Can freeze tokens.
+    pub freezer: AccountId32,
+    /// The total supply across all accounts.
+    pub supply: Balance,
+    /// The balance deposited for this asset. This pays for the data stored
+    /// here.
+    pub deposit: Balance,
+    /// The ED for virtual accounts.
+    pub min_balance: Balance,
+    /// If `true`, then any account with this asset is given a provider
+    /// reference. Otherwise, it requires a consumer reference.
+    pub is_sufficient: bool,
+    /// The total number of accounts.
+    pub accounts: u32,
+    /// The total number of accounts for which we have placed a self-sufficient
+    /// reference.
+    pub sufficients: u32,
+    /// The total number of approvals.
+    pub approvals: u32,
+    /// The status of the asset
+    pub status: AssetStatus,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum AccountStatus {
+    /// Asset account can receive and transfer the assets.
+    Liquid,
+    /// Asset account cannot transfer the assets.
+    Frozen,
+    /// Asset account cannot receive and transfer the assets.
+    Blocked,
+}
+
+/// Why an asset account exists
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum ExistenceReason {
+    /// A consumer reference was used to create this account.
+    Consumer,
+    /// The asset is sufficient, so the account provides for itself.
+    Sufficient,
+    /// The account holder has placed a deposit to exist within an asset
+    /// class.
+    DepositHeld(Balance),
+    /// A deposit was placed for this account to exist, but it has been
+    /// refunded.
+    DepositRefunded,
+    /// Some other account has placed a deposit for this account to exist.
+    DepositFrom(AccountId32, Balance),
+}
+
+/// The balance of an account of an asset
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct AssetAccount {
+    /// The account's balance.
+    pub balance: Balance,
+    /// The status of the account.
+    pub status: AccountStatus,
+    /// The reason for the existence of the account.
+    pub reason: ExistenceReason,
+}
+
+impl<S: Signer, Client: RpcClient, N: HasAssets> Api<'_, S, Client, N> {
+    /// Transfer `amount` of asset `id` to `target`, reaping the signer's
+    /// account if its balance drops below the minimum balance
+    pub fn assets_transfer(
+        &self,
+        id: AssetId,
+        target: GenericAddress,
+        amount: Balance,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedAssetsTransfer>> {
+        let call = (
+            self.call_index([N::ASSETS_PALLET_IDX, N::ASSETS_TRANSFER])?,
+            Compact(id),
+            target,
+            Compact(amount),
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Same as [`Api::assets_transfer`], but fails instead of reaping the
+    /// signer's account
+    pub fn assets_transfer_keep_alive(
+        &self,
+        id: AssetId,
+        target: GenericAddress,
+        amount: Balance,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedAssetsTransfer>> {
+        let call = (
+            self.call_index([N::ASSETS_PALLET_IDX, N::ASSETS_TRANSFER_KEEP_ALIVE])?,
+            Compact(id),
+            target,
+            Compact(amount),
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasAssets> Api<'_, S, Client, N> {
+    /// Returns the details of asset `id`
+    pub fn assets_asset(
+        &self,
+        id: AssetId,
+        at_block: Option<H256>,
+    ) -> Result<Option<AssetDetails>> {
+        let storage_key = storage_assets_asset(id);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the balance of `account` of asset `id`
+    pub fn assets_account<A: Into<AccountId32>>(
+        &self,
+        id: AssetId,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<AssetAccount>> {
+        let storage_key = storage_assets_account(id, account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{AssetHubPolkadot, SubstrateNetwork};
+    use crate::pallets::balances::ComposedTransfer;
+    use crate::pallets::system::{decode_transfers_with_memo, MemoCall};
+    use crate::{
+        AccountId32, ChargeAssetTxPayment, Ed25519Signature, Era, GenericExtra, UncheckedExtrinsic,
+    };
+
+    #[test]
+    fn asset_payment_encode() {
+        let usdt = asset_location::<AssetHubPolkadot>(USDT_ASSET_ID);
+        assert_eq!(usdt.encode(), [0, 2, 4, 50, 5, 0x01, 0x1f]);
+        let payment = ChargeAssetTxPayment {
+            tip: 0,
+            asset_id: Some(usdt),
+        };
+        assert_eq!(payment.encode(), [0, 1, 0, 2, 4, 50, 5, 0x01, 0x1f]);
+
+        // the native token, still with the asset option
+        let extra = GenericExtra::with_asset_payment(Era::Immortal, 1, None);
+        assert_eq!(extra.encode(), [0, 4, 0, 0]);
+        assert_eq!(GenericExtra::new(Era::Immortal, 1).encode(), [0, 4, 0]);
+    }
+
+    #[test]
+    fn asset_payment_decode() {
+        let to: GenericAddress = AccountId32::new([1; 32]).into();
+        let extra = GenericExtra::with_asset_payment(
+            Era::Immortal,
+            1,
+            Some(asset_location::<AssetHubPolkadot>(USDT_ASSET_ID)),
+        );
+        let signature = Some((
+            to.clone(),
+            Ed25519Signature::from_raw([0; 64]).into(),
+            extra,
+        ));
+        let transfer = (
+            [
+                AssetHubPolkadot::BALANCE_PALLET_IDX,
+                AssetHubPolkadot::BALANCE_TRANSFER_KEEP_ALIVE,
+            ],
+            to.clone(),
+            Compact(10),
+        );
+        let xt = UncheckedExtrinsic {
+            signature: signature.clone(),
+            function: transfer.clone(),
+        };
+        let encoded = xt.encode();
+        let decoded: UncheckedExtrinsic<ComposedTransfer> =
+            UncheckedExtrinsic::decode_for::<AssetHubPolkadot, _>(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, xt);
+
+        let xt = UncheckedExtrinsic {
+            signature,
+            function: (
+                [
+                    AssetHubPolkadot::UTILITY_PALLET_IDX,
+                    AssetHubPolkadot::UTILITY_BATCH_ALL,
+                ],
+                vec![
+                    MemoCall::Transfer(transfer),
+                    MemoCall::Remark(([0, 7], b"a".to_vec())),
+                ],
+            ),
+        };
+        let transfers = decode_transfers_with_memo::<AssetHubPolkadot>(&xt.as_hex(), 0).unwrap();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].from, Some(to));
+        assert_eq!(transfers[0].memo, b"a");
+    }
+}
@@ -1,13 +1,109 @@
-use parity_scale_codec::Compact;
+use parity_scale_codec::{Compact, Decode, Encode};
+use sp_core::crypto::AccountId32;
 
 use crate::client::{Api, Result, Signer};
 use crate::network::SubstrateNetwork;
+use crate::pallets::storage::{
+    storage_balances_freezes, storage_balances_holds, storage_balances_locks, storage_value_key,
+};
 use crate::pallets::CallIndex;
-use crate::rpc::RpcClient;
-use crate::{Balance, GenericAddress, UncheckedExtrinsic};
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{AccountInfo, Balance, GenericAddress, UncheckedExtrinsic, H256};
 
 pub type ComposedTransfer = (CallIndex, GenericAddress, Compact<Balance>);
 
+pub type LockIdentifier = [u8; 8];
+
+/// Lock of bonded funds
+pub const STAKING_LOCK_ID: LockIdentifier = *b"staking ";
+/// Lock of funds voted or delegated with conviction
+pub const CONVICTION_VOTING_LOCK_ID: LockIdentifier = *b"pyconvot";
+/// Lock of funds that did not vest yet
+pub const VESTING_LOCK_ID: LockIdentifier = *b"vesting ";
+
+/// Reasons for moving funds out of an account.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum Reasons {
+    /// Paying system transaction fees.
+    Fee,
+    /// Any reason other than paying system transaction fees.
+    Misc,
+    /// Any reason at all.
+    All,
+}
+
+/// A single lock on a balance. There can be many of these on an account and
+/// they "overlap", so the same balance is frozen by multiple locks.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct BalanceLock {
+    /// An identifier for this lock. Only one lock may be in existence for
+    /// each identifier.
+    pub id: LockIdentifier,
+    /// The amount which the free balance may not drop below when this lock is
+    /// in effect.
+    pub amount: Balance,
+    /// If true, then the lock remains in effect even for payment of
+    /// transaction fees.
+    pub reasons: Reasons,
+}
+
+/// A `RuntimeFreezeReason` or `RuntimeHoldReason`: the index of the pallet
+/// variant and the index of the pallet's own reason. All reasons of the
+/// supported runtimes encode this way.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct RuntimeReason {
+    pub pallet: u8,
+    pub reason: u8,
+}
+
+/// An amount frozen or held for a reason
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct IdAmount {
+    pub id: RuntimeReason,
+    pub amount: Balance,
+}
+
+/// Amount of `account` that can be transferred or used for fees.
+///
+/// Locks and freezes overlap, so the largest of them is frozen. Held funds
+/// count towards what is frozen. The existential deposit has to stay in the
+/// account with `keep_alive`, and also when the account cannot be reaped
+/// because other pallets depend on it, e.g. it has a lock or stakes.
+pub fn spendable_balance(
+    info: &AccountInfo,
+    locks: &[BalanceLock],
+    freezes: &[IdAmount],
+    holds: &[IdAmount],
+    existential_deposit: Balance,
+    keep_alive: bool,
+) -> Balance {
+    let account = &info.data;
+    let max_lock = locks.iter().map(|l| l.amount).max().unwrap_or_default();
+    let max_freeze = freezes.iter().map(|f| f.amount).max().unwrap_or_default();
+    let frozen = match account.flags.old_fee_frozen() {
+        None => account.frozen,
+        Some(fee_frozen) => account.frozen.max(fee_frozen),
+    }
+    .max(max_lock)
+    .max(max_freeze);
+    let untouchable = if account.flags.is_new_logic() {
+        let held: Balance = holds.iter().map(|h| h.amount).sum();
+        frozen.saturating_sub(account.reserved.max(held))
+    } else {
+        // reserved funds did not count towards locks before the new logic
+        frozen
+    };
+    // the account keeps its provider reference if it is needed, like
+    // `reducible_balance` does
+    let can_dec_provider = info.consumers == 0 || info.providers > 1;
+    let untouchable = if keep_alive || (account.free > 0 && !can_dec_provider) {
+        untouchable.max(existential_deposit)
+    } else {
+        untouchable
+    };
+    account.free.saturating_sub(untouchable)
+}
+
 impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
     pub fn balance_transfer(
         &self,
@@ -16,10 +112,202 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedTransfer>> {
         let call = (
-            [N::BALANCE_PALLET_IDX, N::BALANCE_TRANSFER],
+            self.call_index([N::BALANCE_PALLET_IDX, N::BALANCE_TRANSFER])?,
+            to,
+            Compact(amount),
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Same as [`Api::balance_transfer`], but fails instead of reaping the
+    /// sender's account
+    pub fn balance_transfer_keep_alive(
+        &self,
+        to: GenericAddress,
+        amount: Balance,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedTransfer>> {
+        let call = (
+            self.call_index([N::BALANCE_PALLET_IDX, N::BALANCE_TRANSFER_KEEP_ALIVE])?,
             to,
             Compact(amount),
         );
         self._create_xt(call, nonce)
     }
 }
+
+impl<S, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
+    /// Returns the locks on the balance of `account`, e.g. staking, conviction
+    /// voting and vesting locks
+    pub fn balance_locks<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Vec<BalanceLock>> {
+        let storage_key = storage_balances_locks(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the freezes on the balance of `account`
+    pub fn balance_freezes<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Vec<IdAmount>> {
+        let storage_key = storage_balances_freezes(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the holds on the balance of `account`
+    pub fn balance_holds<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Vec<IdAmount>> {
+        let storage_key = storage_balances_holds(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the total amount of tokens in existence
+    pub fn balance_total_issuance(&self, at_block: Option<H256>) -> Result<Balance> {
+        let storage_key = storage_value_key("Balances", "TotalIssuance");
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the amount of tokens that are not part of the active issuance,
+    /// e.g. those in crowdloan or treasury accounts
+    pub fn balance_inactive_issuance(&self, at_block: Option<H256>) -> Result<Balance> {
+        let storage_key = storage_value_key("Balances", "InactiveIssuance");
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the total issuance less the inactive issuance, which the
+    /// runtime measures the support of referenda against
+    pub fn balance_active_issuance(&self, at_block: Option<H256>) -> Result<Balance> {
+        let total = self.balance_total_issuance(at_block)?;
+        let inactive = self.balance_inactive_issuance(at_block)?;
+        Ok(total.saturating_sub(inactive))
+    }
+
+    /// Returns the amount `account` can transfer, see [`spendable_balance`]
+    pub fn balance_spendable<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        keep_alive: bool,
+        at_block: Option<H256>,
+    ) -> Result<Balance> {
+        let account = account.into();
+        let Some(info) = self.account_info(account.clone(), at_block)? else {
+            return Ok(0);
+        };
+        let locks = self.balance_locks(account.clone(), at_block)?;
+        let freezes = self.balance_freezes(account.clone(), at_block)?;
+        let holds = self.balance_holds(account, at_block)?;
+        Ok(spendable_balance(
+            &info,
+            &locks,
+            &freezes,
+            &holds,
+            N::BALANCE_EXISTENTIAL_DEPOSIT,
+            keep_alive,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountData, ExtraFlags};
+
+    fn account(free: Balance, reserved: Balance, frozen: Balance) -> AccountInfo {
+        AccountInfo {
+            nonce: 0,
+            consumers: 0,
+            providers: 1,
+            sufficients: 0,
+            data: AccountData {
+                free,
+                reserved,
+                frozen,
+                flags: ExtraFlags::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn lock_decode() {
+        let encoded = hex::decode("7374616b696e6720e803000000000000000000000000000002").unwrap();
+        assert_eq!(
+            BalanceLock::decode(&mut encoded.as_slice()).unwrap(),
+            BalanceLock {
+                id: STAKING_LOCK_ID,
+                amount: 1_000,
+                reasons: Reasons::All,
+            }
+        );
+    }
+
+    #[test]
+    fn spendable() {
+        let lock = |amount| BalanceLock {
+            id: STAKING_LOCK_ID,
+            amount,
+            reasons: Reasons::All,
+        };
+        // frozen by a lock not reflected in `frozen` yet
+        let data = account(1_000, 0, 0);
+        assert_eq!(
+            spendable_balance(&data, &[lock(600)], &[], &[], 10, false),
+            400
+        );
+        // locks overlap
+        let locks = [lock(600), lock(300)];
+        assert_eq!(spendable_balance(&data, &locks, &[], &[], 10, false), 400);
+        // held funds count towards the frozen amount
+        let data = account(1_000, 200, 600);
+        assert_eq!(spendable_balance(&data, &[], &[], &[], 10, false), 600);
+        // the existential deposit stays with keep alive
+        let data = account(1_000, 0, 0);
+        assert_eq!(spendable_balance(&data, &[], &[], &[], 10, true), 990);
+        // old logic: `misc_frozen` and `fee_frozen`, reserved does not count
+        let mut data = account(1_000, 200, 600);
+        data.data.flags = ExtraFlags::old_logic();
+        assert_eq!(spendable_balance(&data, &[], &[], &[], 10, false), 400);
+        // a lock adds a consumer, so the account cannot be reaped
+        let mut data = account(1_000, 0, 0);
+        data.consumers = 1;
+        assert_eq!(spendable_balance(&data, &[], &[], &[], 10, false), 990);
+        assert_eq!(
+            spendable_balance(&data, &[lock(995)], &[], &[], 10, false),
+            5
+        );
+        // unless another provider keeps it alive
+        data.providers = 2;
+        assert_eq!(spendable_balance(&data, &[], &[], &[], 10, false), 1_000);
+    }
+}
@@ -0,0 +1,391 @@
+use parity_scale_codec::{Compact, Decode, Encode, Error, Input, Output};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasConvictionVoting;
+use crate::pallets::storage::{
+    storage_conviction_voting_class_locks_for, storage_conviction_voting_voting_for,
+};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, GenericAddress, Perbill, UncheckedExtrinsic, H256};
+
+pub type PollIndex = u32;
+/// A referendum track
+pub type Class = u16;
+
+pub type ComposedConvictionVotingVote = (CallIndex, Compact<PollIndex>, AccountVote);
+pub type ComposedConvictionVotingDelegate = (CallIndex, Class, GenericAddress, Conviction, Balance);
+pub type ComposedConvictionVotingUndelegate = (CallIndex, Class);
+pub type ComposedConvictionVotingUnlock = (CallIndex, Class, GenericAddress);
+pub type ComposedConvictionVotingRemoveVote = (CallIndex, Option<Class>, PollIndex);
+
+/// A value denoting the strength of conviction of a vote.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, PartialOrd, Ord, Encode, Decode)]
+pub enum Conviction {
+    /// 0.1x votes, unlocked.
+    #[default]
+    None,
+    /// 1x votes, locked for an enactment period following a successful vote.
+    Locked1x,
+    /// 2x votes, locked for 2x enactment periods following a successful vote.
+    Locked2x,
+    /// 3x votes, locked for 4x...
+    Locked3x,
+    /// 4x votes, locked for 8x...
+    Locked4x,
+    /// 5x votes, locked for 16x...
+    Locked5x,
+    /// 6x votes, locked for 32x...
+    Locked6x,
+}
+
+impl Conviction {
+    /// The amount of time (in number of vote locking periods) that our
+    /// conviction implies a successful voter's balance should be locked for.
+    pub fn lock_periods(self) -> u32 {
+        match self {
+            Conviction::None => 0,
+            Conviction::Locked1x => 1,
+            Conviction::Locked2x => 2,
+            Conviction::Locked3x => 4,
+            Conviction::Locked4x => 8,
+            Conviction::Locked5x => 16,
+            Conviction::Locked6x => 32,
+        }
+    }
+
+    /// The votes of a voter who votes with `capital` at this conviction
+    pub fn votes(self, capital: Balance) -> Balance {
+        match self {
+            Conviction::None => capital / 10,
+            x => capital.saturating_mul(x as Balance),
+        }
+    }
+}
+
+impl TryFrom<u8> for Conviction {
+    type Error = &'static str;
+
+    fn try_from(i: u8) -> std::result::Result<Self, Self::Error> {
+        Ok(match i {
+            0 => Conviction::None,
+            1 => Conviction::Locked1x,
+            2 => Conviction::Locked2x,
+            3 => Conviction::Locked3x,
+            4 => Conviction::Locked4x,
+            5 => Conviction::Locked5x,
+            6 => Conviction::Locked6x,
+            _ => return Err("Invalid conviction"),
+        })
+    }
+}
+
+/// A number of lock periods, plus a vote, one way or the other. Encoded as a
+/// single byte: the MSB is set for aye, the rest is the conviction.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Vote {
+    pub aye: bool,
+    pub conviction: Conviction,
+}
+
+impl Encode for Vote {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        output.push_byte(self.conviction as u8 | if self.aye { 0x80 } else { 0 });
+    }
+}
+
+impl Decode for Vote {
+    fn decode<I: Input>(input: &mut I) -> std::result::Result<Self, Error> {
+        let b = input.read_byte()?;
+        Ok(Vote {
+            aye: b & 0x80 == 0x80,
+            conviction: Conviction::try_from(b & 0x7f).map_err(Error::from)?,
+        })
+    }
+}
+
+/// A vote for a referendum of a particular account.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum AccountVote {
+    /// A standard vote, one-way (approve or reject) with a given amount of
+    /// conviction.
+    Standard { vote: Vote, balance: Balance },
+    /// A split vote with balances given for both ways, and with no
+    /// conviction, useful for parachains when voting.
+    Split { aye: Balance, nay: Balance },
+    /// A split vote with balances given for both ways as well as abstentions,
+    /// and with no conviction, useful for parachains when voting, other
+    /// off-chain aggregate accounts and individuals who wish to abstain.
+    SplitAbstain {
+        aye: Balance,
+        nay: Balance,
+        abstain: Balance,
+    },
+}
+
+/// Info regarding an ongoing referendum.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct Tally {
+    /// The number of aye votes, expressed in terms of post-conviction
+    /// lock-vote.
+    pub ayes: Balance,
+    /// The number of nay votes, expressed in terms of post-conviction
+    /// lock-vote.
+    pub nays: Balance,
+    /// The basic number of aye votes, expressed pre-conviction.
+    pub support: Balance,
+}
+
+impl Tally {
+    /// Share of aye votes out of all aye and nay votes
+    pub fn approval(&self) -> Perbill {
+        Perbill::from_rational(self.ayes, self.ayes.saturating_add(self.nays))
+    }
+
+    /// Share of `active_issuance` that voted aye, before conviction. The
+    /// runtime measures support against the total issuance less the inactive
+    /// issuance, see [`Api::balance_active_issuance`].
+    pub fn support(&self, active_issuance: Balance) -> Perbill {
+        Perbill::from_rational(self.support, active_issuance)
+    }
+}
+
+/// Amount of votes and capital placed in delegation for an account.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct Delegations {
+    /// The number of votes (this is post-conviction).
+    pub votes: Balance,
+    /// The amount of raw capital, used for the support.
+    pub capital: Balance,
+}
+
+/// A lock that is still in place after a vote was removed: the block it
+/// expires at and the amount.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct PriorLock(pub u32, pub Balance);
+
+/// Information concerning the direct vote-casting of some voting power.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Encode, Decode)]
+pub struct Casting {
+    /// The current votes of the account.
+    pub votes: Vec<(PollIndex, AccountVote)>,
+    /// The total amount of delegations that this account has received.
+    pub delegations: Delegations,
+    /// Any pre-existing locks from past voting/delegating activity.
+    pub prior: PriorLock,
+}
+
+/// Information concerning the delegation of some voting power.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Delegating {
+    /// The amount of balance delegated.
+    pub balance: Balance,
+    /// The account to which the voting power is delegated.
+    pub target: AccountId32,
+    /// The conviction with which the voting power is delegated. When this
+    /// gets undelegated, the relevant lock begins.
+    pub conviction: Conviction,
+    /// The total amount of delegations that this account has received.
+    pub delegations: Delegations,
+    /// Any pre-existing locks from past voting/delegating activity.
+    pub prior: PriorLock,
+}
+
+/// An indicator for what an account is doing; it can either be delegating or
+/// voting.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum Voting {
+    /// The account is voting directly.
+    Casting(Casting),
+    /// The account is delegating `balance` of its balance to a `target`
+    /// account with `conviction`.
+    Delegating(Delegating),
+}
+
+impl<S: Signer, Client: RpcClient, N: HasConvictionVoting> Api<'_, S, Client, N> {
+    /// Votes on referendum `poll_index`, replacing any earlier vote on it
+    pub fn conviction_voting_vote(
+        &self,
+        poll_index: PollIndex,
+        vote: AccountVote,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedConvictionVotingVote>> {
+        let call = (
+            self.call_index([N::CONVICTION_VOTING_PALLET_IDX, N::CONVICTION_VOTING_VOTE])?,
+            Compact(poll_index),
+            vote,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Delegates the voting power of `balance` on track `class` to `to`
+    pub fn conviction_voting_delegate(
+        &self,
+        class: Class,
+        to: GenericAddress,
+        conviction: Conviction,
+        balance: Balance,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedConvictionVotingDelegate>> {
+        let call = (
+            self.call_index([
+                N::CONVICTION_VOTING_PALLET_IDX,
+                N::CONVICTION_VOTING_DELEGATE,
+            ])?,
+            class,
+            to,
+            conviction,
+            balance,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Undelegates the voting power on track `class`. The balance stays
+    /// locked for the conviction's lock periods.
+    pub fn conviction_voting_undelegate(
+        &self,
+        class: Class,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedConvictionVotingUndelegate>> {
+        let call = (
+            self.call_index([
+                N::CONVICTION_VOTING_PALLET_IDX,
+                N::CONVICTION_VOTING_UNDELEGATE,
+            ])?,
+            class,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Removes the expired locks of `target` on track `class`
+    pub fn conviction_voting_unlock(
+        &self,
+        class: Class,
+        target: GenericAddress,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedConvictionVotingUnlock>> {
+        let call = (
+            self.call_index([N::CONVICTION_VOTING_PALLET_IDX, N::CONVICTION_VOTING_UNLOCK])?,
+            class,
+            target,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Removes the signer's vote on referendum `index`. `class` is required
+    /// if the referendum is not ongoing anymore.
+    pub fn conviction_voting_remove_vote(
+        &self,
+        class: Option<Class>,
+        index: PollIndex,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedConvictionVotingRemoveVote>> {
+        let call = (
+            self.call_index([
+                N::CONVICTION_VOTING_PALLET_IDX,
+                N::CONVICTION_VOTING_REMOVE_VOTE,
+            ])?,
+            class,
+            index,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasConvictionVoting> Api<'_, S, Client, N> {
+    /// Returns the votes or delegation of `account` on track `class`
+    pub fn conviction_voting_voting_for<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        class: Class,
+        at_block: Option<H256>,
+    ) -> Result<Option<Voting>> {
+        let storage_key = storage_conviction_voting_voting_for(account.into().as_ref(), class);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the tracks `account` has locks on and the amount locked on
+    /// each. A lock can only be removed with `unlock` once the votes causing
+    /// it are removed and their lock periods expired.
+    pub fn conviction_voting_class_locks_for<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Vec<(Class, Balance)>> {
+        let storage_key = storage_conviction_voting_class_locks_for(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vote_encode() {
+        let aye = Vote {
+            aye: true,
+            conviction: Conviction::Locked3x,
+        };
+        assert_eq!(aye.encode(), vec![0x83]);
+        let nay = Vote {
+            aye: false,
+            conviction: Conviction::None,
+        };
+        assert_eq!(nay.encode(), vec![0x00]);
+        assert_eq!(
+            Vote::decode(&mut [0x86].as_slice()).unwrap().conviction,
+            Conviction::Locked6x
+        );
+        assert!(Vote::decode(&mut [0x87].as_slice()).is_err());
+
+        let vote = AccountVote::Standard {
+            vote: aye,
+            balance: 1_000,
+        };
+        assert_eq!(
+            hex::encode(vote.encode()),
+            "0083e8030000000000000000000000000000"
+        );
+        let vote = AccountVote::SplitAbstain {
+            aye: 1,
+            nay: 2,
+            abstain: 3,
+        };
+        assert_eq!(
+            hex::encode(vote.encode()),
+            concat!(
+                "02",
+                "01000000000000000000000000000000",
+                "02000000000000000000000000000000",
+                "03000000000000000000000000000000"
+            )
+        );
+    }
+
+    #[test]
+    fn conviction_votes() {
+        assert_eq!(Conviction::None.votes(1_000), 100);
+        assert_eq!(Conviction::Locked1x.votes(1_000), 1_000);
+        assert_eq!(Conviction::Locked6x.votes(1_000), 6_000);
+        assert_eq!(Conviction::Locked6x.lock_periods(), 32);
+    }
+
+    #[test]
+    fn tally_shares() {
+        let tally = Tally {
+            ayes: 300,
+            nays: 100,
+            support: 50,
+        };
+        assert_eq!(tally.approval(), Perbill::from_percent(75));
+        assert_eq!(tally.support(1_000 - 500), Perbill::from_percent(10));
+    }
+}
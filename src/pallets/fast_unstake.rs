@@ -0,0 +1,153 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasFastUnstake;
+use crate::pallets::staking::EraIndex;
+use crate::pallets::storage::{storage_fast_unstake_queue, storage_value_key};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, UncheckedExtrinsic, H256};
+
+pub type ComposedFastUnstakeRegisterFastUnstake = CallIndex;
+pub type ComposedFastUnstakeDeregister = CallIndex;
+
+/// An unstake request being checked
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/fast-unstake/src/types.rs
+#[derive(Debug, Clone, Default, Eq, PartialEq, Encode, Decode)]
+pub struct UnstakeRequest {
+    /// This list of stashes are being processed in this request, and their
+    /// corresponding deposit.
+    pub stashes: Vec<(AccountId32, Balance)>,
+    /// The list of eras for which they have been checked.
+    pub checked: Vec<EraIndex>,
+}
+
+/// Where a stash is in the fast unstake process
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FastUnstakeStatus {
+    /// The stash did not register or was already unstaked
+    NotRegistered,
+    /// The stash waits in the queue to be checked
+    Queued { deposit: Balance },
+    /// The stash is being checked
+    Checking {
+        deposit: Balance,
+        /// Eras that still have to be checked for exposure
+        eras_left: u32,
+        /// Blocks until the check completes, `None` if checking is paused
+        blocks_left: Option<u32>,
+    },
+}
+
+/// Blocks needed to check `eras_left` eras at `eras_per_block`, `None` if
+/// checking is paused.
+pub fn blocks_to_check(eras_left: u32, eras_per_block: u32) -> Option<u32> {
+    (eras_per_block > 0).then(|| eras_left.div_ceil(eras_per_block))
+}
+
+impl<S: Signer, Client: RpcClient, N: HasFastUnstake> Api<'_, S, Client, N> {
+    /// Registers the signer's stash to be unstaked once it is checked not to
+    /// be exposed in the last `STAKING_BONDING_DURATION` eras. Reserves a
+    /// deposit that is slashed if it was exposed. Must be signed by the
+    /// controller.
+    pub fn fast_unstake_register_fast_unstake(
+        &self,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedFastUnstakeRegisterFastUnstake>> {
+        self._create_xt(
+            self.call_index([
+                N::FAST_UNSTAKE_PALLET_IDX,
+                N::FAST_UNSTAKE_REGISTER_FAST_UNSTAKE,
+            ])?,
+            nonce,
+        )
+    }
+
+    /// Removes the signer's stash from the queue and returns its deposit.
+    /// Fails once the stash is being checked.
+    pub fn fast_unstake_deregister(
+        &self,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedFastUnstakeDeregister>> {
+        self._create_xt(
+            self.call_index([N::FAST_UNSTAKE_PALLET_IDX, N::FAST_UNSTAKE_DEREGISTER])?,
+            nonce,
+        )
+    }
+}
+
+impl<S, Client: RpcClient, N: HasFastUnstake> Api<'_, S, Client, N> {
+    /// Returns the deposit of `stash` if it waits in the queue
+    pub fn fast_unstake_queue<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<Balance>> {
+        let storage_key = storage_fast_unstake_queue(stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the request being checked
+    pub fn fast_unstake_head(&self, at_block: Option<H256>) -> Result<Option<UnstakeRequest>> {
+        let storage_key = storage_value_key("FastUnstake", "Head");
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the number of eras checked per block, 0 if fast unstake is
+    /// paused
+    pub fn fast_unstake_eras_to_check_per_block(&self, at_block: Option<H256>) -> Result<u32> {
+        let storage_key = storage_value_key("FastUnstake", "ErasToCheckPerBlock");
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns whether `stash` is queued or being checked, and how many
+    /// blocks are left until it is unstaked
+    pub fn fast_unstake_status<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<FastUnstakeStatus> {
+        let stash = stash.into();
+        if let Some(deposit) = self.fast_unstake_queue(stash.clone(), at_block)? {
+            return Ok(FastUnstakeStatus::Queued { deposit });
+        }
+        let Some(head) = self.fast_unstake_head(at_block)? else {
+            return Ok(FastUnstakeStatus::NotRegistered);
+        };
+        let Some((_, deposit)) = head.stashes.iter().find(|(s, _)| *s == stash) else {
+            return Ok(FastUnstakeStatus::NotRegistered);
+        };
+        // eras `current_era - bonding_duration ..= current_era` are checked
+        let eras_left = (N::STAKING_BONDING_DURATION + 1).saturating_sub(head.checked.len() as u32);
+        let eras_per_block = self.fast_unstake_eras_to_check_per_block(at_block)?;
+        Ok(FastUnstakeStatus::Checking {
+            deposit: *deposit,
+            eras_left,
+            blocks_left: blocks_to_check(eras_left, eras_per_block),
+        })
+    }
+
+    /// Whether `stash` can register for fast unstake: it must be bonded,
+    /// have nothing unlocking and not be queued yet. Exposure in the last
+    /// `STAKING_BONDING_DURATION` eras is only checked on chain.
+    pub fn fast_unstake_can_register<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<bool> {
+        let stash = stash.into();
+        let Some(ledger) = self.staking_stash_ledger(stash.clone(), at_block)? else {
+            return Ok(false);
+        };
+        Ok(ledger.unlocking.is_empty()
+            && self.fast_unstake_status(stash, at_block)? == FastUnstakeStatus::NotRegistered)
+    }
+}
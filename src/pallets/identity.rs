@@ -1,18 +1,349 @@
+use parity_scale_codec::{Compact, Decode, Encode, Error, Input, Output};
+use sp_core::crypto::AccountId32;
+
 use crate::client::{Api, Result, Signer};
-use crate::network::SubstrateNetwork;
+use crate::network::HasIdentity;
+use crate::pallets::storage::{storage_identity_identity_of, storage_identity_super_of};
 use crate::pallets::CallIndex;
-use crate::rpc::RpcClient;
-use crate::UncheckedExtrinsic;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, UncheckedExtrinsic, H256};
+
+pub type ComposedIdentitySetIdentity = (CallIndex, IdentityInfo);
+pub type ComposedIdentitySetSubs = (CallIndex, Vec<(AccountId32, Data)>);
+pub type ComposedIdentityClearIdentity = CallIndex;
+pub type ComposedIdentityRequestJudgement = (CallIndex, Compact<RegistrarIndex>, Compact<Balance>);
+
+/// An index of a registrar.
+pub type RegistrarIndex = u32;
+
+/// An attestation of a registrar over how accurate some `IdentityInfo` is in
+/// describing an account.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/identity/src/types.rs
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum Judgement {
+    /// The default value; no opinion is held.
+    Unknown,
+    /// No judgement is yet in place, but a deposit is reserved as payment for
+    /// providing one.
+    FeePaid(Balance),
+    /// The data appears to be reasonably acceptable in terms of its accuracy.
+    Reasonable,
+    /// The target is known directly by the registrar and the registrar can
+    /// fully attest to the data's accuracy.
+    KnownGood,
+    /// The data was once good but is currently out of date.
+    OutOfDate,
+    /// The data is imprecise or of sufficiently low-quality to be
+    /// problematic.
+    LowQuality,
+    /// The data is erroneous. This may be indicative of malicious intent.
+    Erroneous,
+}
 
-pub type ComposedJoinIdentity = (CallIndex, u64);
+impl Judgement {
+    /// Whether the registrar vouched for the identity
+    pub fn is_verified(&self) -> bool {
+        matches!(self, Judgement::Reasonable | Judgement::KnownGood)
+    }
+}
+
+/// Either underlying data blob if it is at most 32 bytes, or a hash of it.
+/// If the data is greater than 32-bytes then it will be truncated when
+/// encoding.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum Data {
+    /// No data here.
+    #[default]
+    None,
+    /// The data is stored directly.
+    Raw(Vec<u8>),
+    /// Only the Blake2 hash of the data is stored.
+    BlakeTwo256([u8; 32]),
+    /// Only the SHA2-256 hash of the data is stored.
+    Sha256([u8; 32]),
+    /// Only the Keccak-256 hash of the data is stored.
+    Keccak256([u8; 32]),
+    /// Only the SHA3-256 hash of the data is stored.
+    ShaThree256([u8; 32]),
+}
 
-impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
-    pub fn join_identity_as_key(
+impl Data {
+    /// The data as text, if it is stored directly and valid UTF-8
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Data::Raw(raw) => std::str::from_utf8(raw).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Data {
+    fn from(s: &str) -> Self {
+        if s.is_empty() {
+            Data::None
+        } else {
+            Data::Raw(s.as_bytes().to_vec())
+        }
+    }
+}
+
+impl Encode for Data {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        match self {
+            Data::None => output.push_byte(0),
+            Data::Raw(raw) => {
+                let raw = &raw[..raw.len().min(32)];
+                output.push_byte(raw.len() as u8 + 1);
+                output.write(raw);
+            }
+            Data::BlakeTwo256(h) => {
+                output.push_byte(34);
+                output.write(h);
+            }
+            Data::Sha256(h) => {
+                output.push_byte(35);
+                output.write(h);
+            }
+            Data::Keccak256(h) => {
+                output.push_byte(36);
+                output.write(h);
+            }
+            Data::ShaThree256(h) => {
+                output.push_byte(37);
+                output.write(h);
+            }
+        }
+    }
+}
+
+impl Decode for Data {
+    fn decode<I: Input>(input: &mut I) -> std::result::Result<Self, Error> {
+        let b = input.read_byte()?;
+        Ok(match b {
+            0 => Data::None,
+            1..=33 => {
+                let mut raw = vec![0; b as usize - 1];
+                input.read(&mut raw)?;
+                Data::Raw(raw)
+            }
+            34 => Data::BlakeTwo256(Decode::decode(input)?),
+            35 => Data::Sha256(Decode::decode(input)?),
+            36 => Data::Keccak256(Decode::decode(input)?),
+            37 => Data::ShaThree256(Decode::decode(input)?),
+            _ => return Err("Invalid identity data".into()),
+        })
+    }
+}
+
+/// Information concerning the identity of the controller of an account, as
+/// laid out by the People chains.
+/// https://github.com/polkadot-fellows/runtimes/blob/main/system-parachains/people/people-polkadot/src/people.rs
+#[derive(Debug, Clone, Default, Eq, PartialEq, Encode, Decode)]
+pub struct IdentityInfo {
+    /// A reasonable display name for the controller of the account.
+    pub display: Data,
+    /// The full legal name in the local jurisdiction of the entity.
+    pub legal: Data,
+    /// A representative website held by the controller of the account.
+    pub web: Data,
+    /// The Matrix (e.g. for Element) handle held by the controller of the
+    /// account.
+    pub matrix: Data,
+    /// The email address of the controller of the account.
+    pub email: Data,
+    /// The PGP/GPG public key of the controller of the account.
+    pub pgp_fingerprint: Option<[u8; 20]>,
+    /// A graphic image representing the controller of the account.
+    pub image: Data,
+    /// The Twitter identity.
+    pub twitter: Data,
+    /// The GitHub username of the controller of the account.
+    pub github: Data,
+    /// The Discord username of the controller of the account.
+    pub discord: Data,
+}
+
+/// Information concerning the identity of the controller of an account.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Registration {
+    /// Judgements from the registrars on this identity.
+    pub judgements: Vec<(RegistrarIndex, Judgement)>,
+    /// Amount held on deposit for this information.
+    pub deposit: Balance,
+    /// Information on the identity.
+    pub info: IdentityInfo,
+}
+
+/// The leading fields of an identity `Registration`, which do not depend on
+/// the identity info layout of a runtime.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct IdentityJudgements {
+    /// Judgements from the registrars on this identity.
+    pub judgements: Vec<(RegistrarIndex, Judgement)>,
+    /// Amount held on deposit for this information.
+    pub deposit: Balance,
+}
+
+impl IdentityJudgements {
+    /// Whether any registrar vouched for the identity
+    pub fn is_verified(&self) -> bool {
+        self.judgements.iter().any(|(_, j)| j.is_verified())
+    }
+}
+
+impl<S: Signer, Client: RpcClient, N: HasIdentity> Api<'_, S, Client, N> {
+    /// Sets the identity of the signer, replacing any earlier one and its
+    /// judgements. Reserves a deposit.
+    pub fn identity_set_identity(
         &self,
-        auth_id: u64,
+        info: IdentityInfo,
         nonce: Option<u32>,
-    ) -> Result<UncheckedExtrinsic<ComposedJoinIdentity>> {
-        let call = ([N::IDENTITY_PALLET_IDX, N::IDENTITY_JOIN_AS_KEY], auth_id);
+    ) -> Result<UncheckedExtrinsic<ComposedIdentitySetIdentity>> {
+        let call = (
+            self.call_index([N::IDENTITY_PALLET_IDX, N::IDENTITY_SET_IDENTITY])?,
+            info,
+        );
         self._create_xt(call, nonce)
     }
+
+    /// Sets the sub-accounts of the signer and their names, replacing any
+    /// earlier ones
+    pub fn identity_set_subs(
+        &self,
+        subs: Vec<(AccountId32, Data)>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIdentitySetSubs>> {
+        let call = (
+            self.call_index([N::IDENTITY_PALLET_IDX, N::IDENTITY_SET_SUBS])?,
+            subs,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Clears the identity and sub-accounts of the signer and returns their
+    /// deposits
+    pub fn identity_clear_identity(
+        &self,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIdentityClearIdentity>> {
+        self._create_xt(
+            self.call_index([N::IDENTITY_PALLET_IDX, N::IDENTITY_CLEAR_IDENTITY])?,
+            nonce,
+        )
+    }
+
+    /// Requests a judgement of the signer's identity from registrar
+    /// `reg_index`, paying it at most `max_fee`
+    pub fn identity_request_judgement(
+        &self,
+        reg_index: RegistrarIndex,
+        max_fee: Balance,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIdentityRequestJudgement>> {
+        let call = (
+            self.call_index([N::IDENTITY_PALLET_IDX, N::IDENTITY_REQUEST_JUDGEMENT])?,
+            Compact(reg_index),
+            Compact(max_fee),
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasIdentity> Api<'_, S, Client, N> {
+    /// Returns the registrar judgements of the identity set for `account`
+    pub fn identity_judgements<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<IdentityJudgements>> {
+        let storage_key = storage_identity_identity_of(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the identity set for `account`
+    pub fn identity_identity_of<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<Registration>> {
+        let storage_key = storage_identity_identity_of(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the parent account of sub-account `account` and its name
+    pub fn identity_super_of<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<(AccountId32, Data)>> {
+        let storage_key = storage_identity_super_of(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the display name of `account`. A sub-account is shown as
+    /// `parent/sub` from its parent's display name and its own name.
+    pub fn identity_display_name<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<String>> {
+        let account = account.into();
+        if let Some(display) = self.display_of(account.clone(), at_block)? {
+            return Ok(Some(display));
+        }
+        let Some((parent, sub)) = self.identity_super_of(account, at_block)? else {
+            return Ok(None);
+        };
+        let sub = sub.as_str().map(str::to_owned);
+        Ok(match (self.display_of(parent, at_block)?, sub) {
+            (Some(parent), Some(sub)) => Some(format!("{parent}/{sub}")),
+            (parent, sub) => parent.or(sub),
+        })
+    }
+
+    fn display_of(&self, account: AccountId32, at_block: Option<H256>) -> Result<Option<String>> {
+        Ok(self
+            .identity_identity_of(account, at_block)?
+            .and_then(|r| r.info.display.as_str().map(str::to_owned)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_codec() {
+        assert_eq!(Data::from("").encode(), vec![0]);
+        assert_eq!(Data::from("abc").encode(), vec![4, b'a', b'b', b'c']);
+        let data = Data::decode(&mut [4, b'a', b'b', b'c'].as_slice()).unwrap();
+        assert_eq!(data.as_str(), Some("abc"));
+        let hash = Data::decode(&mut [[34].as_slice(), &[7; 32]].concat().as_slice()).unwrap();
+        assert_eq!(hash, Data::BlakeTwo256([7; 32]));
+        assert!(Data::decode(&mut [38].as_slice()).is_err());
+    }
+
+    #[test]
+    fn registration_decode() {
+        let registration = Registration {
+            judgements: vec![(0, Judgement::KnownGood)],
+            deposit: 1_000,
+            info: IdentityInfo {
+                display: "Alice".into(),
+                twitter: "@alice".into(),
+                ..Default::default()
+            },
+        };
+        let encoded = registration.encode();
+        assert_eq!(
+            Registration::decode(&mut encoded.as_slice()).unwrap(),
+            registration
+        );
+        // the prefix decodes as judgements
+        let judgements = IdentityJudgements::decode(&mut encoded.as_slice()).unwrap();
+        assert!(judgements.is_verified());
+    }
 }
@@ -0,0 +1,123 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasIndices;
+use crate::pallets::storage::storage_indices_accounts;
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, GenericAddress, MultiAddress, UncheckedExtrinsic, H256};
+
+pub type AccountIndex = u32;
+
+pub type ComposedIndicesClaim = (CallIndex, AccountIndex);
+pub type ComposedIndicesTransfer = (CallIndex, GenericAddress, AccountIndex);
+pub type ComposedIndicesFree = (CallIndex, AccountIndex);
+
+/// The owner of an index, the deposit reserved for it and whether it is
+/// frozen
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct IndexOwner {
+    pub account: AccountId32,
+    pub deposit: Balance,
+    pub frozen: bool,
+}
+
+/// The index type of a `MultiAddress`. The relay chains look up addresses
+/// with `()`, so their `Index` variant carries no index to resolve.
+pub trait MaybeAccountIndex {
+    fn account_index(&self) -> Option<AccountIndex>;
+}
+
+impl MaybeAccountIndex for () {
+    fn account_index(&self) -> Option<AccountIndex> {
+        None
+    }
+}
+
+impl MaybeAccountIndex for AccountIndex {
+    fn account_index(&self) -> Option<AccountIndex> {
+        Some(*self)
+    }
+}
+
+impl<S: Signer, Client: RpcClient, N: HasIndices> Api<'_, S, Client, N> {
+    /// Assign a previously-unassigned index to the signer, reserving a
+    /// deposit
+    pub fn indices_claim(
+        &self,
+        index: AccountIndex,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIndicesClaim>> {
+        let call = (
+            self.call_index([N::INDICES_PALLET_IDX, N::INDICES_CLAIM])?,
+            index,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Assign an index owned by the signer to another account, moving the
+    /// deposit
+    pub fn indices_transfer(
+        &self,
+        new: GenericAddress,
+        index: AccountIndex,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIndicesTransfer>> {
+        let call = (
+            self.call_index([N::INDICES_PALLET_IDX, N::INDICES_TRANSFER])?,
+            new,
+            index,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Free up an index owned by the signer, unreserving the deposit
+    pub fn indices_free(
+        &self,
+        index: AccountIndex,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIndicesFree>> {
+        let call = (
+            self.call_index([N::INDICES_PALLET_IDX, N::INDICES_FREE])?,
+            index,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasIndices> Api<'_, S, Client, N> {
+    /// Returns the owner of `index`
+    pub fn indices_accounts(
+        &self,
+        index: AccountIndex,
+        at_block: Option<H256>,
+    ) -> Result<Option<IndexOwner>> {
+        let storage_key = storage_indices_accounts(index);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the account `address` refers to. Indices are looked up,
+    /// 32 byte raw addresses are taken as the account. `None` if the
+    /// address is a 20 byte address, an unassigned index or an index that
+    /// is not carried by the address.
+    pub fn indices_resolve<I: MaybeAccountIndex>(
+        &self,
+        address: &MultiAddress<AccountId32, I>,
+        at_block: Option<H256>,
+    ) -> Result<Option<AccountId32>> {
+        Ok(match address {
+            MultiAddress::Id(account) => Some(account.clone()),
+            MultiAddress::Address32(raw) => Some(AccountId32::new(*raw)),
+            MultiAddress::Raw(raw) => <[u8; 32]>::try_from(raw.as_slice())
+                .ok()
+                .map(AccountId32::new),
+            MultiAddress::Index(index) => match index.account_index() {
+                Some(index) => self.indices_accounts(index, at_block)?.map(|o| o.account),
+                None => None,
+            },
+            MultiAddress::Address20(_) => None,
+        })
+    }
+}
@@ -0,0 +1,227 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasMultisig;
+use crate::pallets::storage::{storage_multisig_multisigs, storage_multisig_multisigs_prefix};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{blake2_256, Balance, UncheckedExtrinsic, Weight, H256};
+
+pub type ComposedMultisigAsMultiThreshold1<Call> = (CallIndex, Vec<AccountId32>, Call);
+pub type ComposedMultisigAsMulti<Call> = (
+    CallIndex,
+    u16,
+    Vec<AccountId32>,
+    Option<Timepoint>,
+    Call,
+    Weight,
+);
+pub type ComposedMultisigApproveAsMulti = (
+    CallIndex,
+    u16,
+    Vec<AccountId32>,
+    Option<Timepoint>,
+    [u8; 32],
+    Weight,
+);
+pub type ComposedMultisigCancelAsMulti = (CallIndex, u16, Vec<AccountId32>, Timepoint, [u8; 32]);
+
+/// A global extrinsic index, formed as the extrinsic index within a block,
+/// together with that block's height.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/multisig/src/lib.rs
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct Timepoint {
+    /// The height of the chain at the point in time.
+    pub height: u32,
+    /// The index of the extrinsic at the point in time.
+    pub index: u32,
+}
+
+/// An open multisig operation.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Multisig {
+    /// The extrinsic when the multisig operation was opened.
+    pub when: Timepoint,
+    /// The amount held in reserve of the `depositor`, to be returned once the
+    /// operation ends.
+    pub deposit: Balance,
+    /// The account who opened it (i.e. the first to approve it).
+    pub depositor: AccountId32,
+    /// The approvals achieved so far, including the depositor. Always sorted.
+    pub approvals: Vec<AccountId32>,
+}
+
+/// Derive the multi-account ID from the given signatories and threshold.
+///
+/// The order of `signatories` does not matter. Use
+/// [`Ss58Codec`](crate::ss58::Ss58Codec) to format the result for a network.
+pub fn multi_account_id(signatories: &[AccountId32], threshold: u16) -> AccountId32 {
+    let mut who = signatories.to_vec();
+    who.sort();
+    (b"modlpy/utilisuba", who, threshold)
+        .using_encoded(blake2_256)
+        .into()
+}
+
+/// The hash a multisig operation is keyed by, i.e. `blake2_256` of the encoded
+/// call.
+pub fn call_hash<Call: Encode>(call: &Call) -> [u8; 32] {
+    call.using_encoded(blake2_256)
+}
+
+fn sorted(mut signatories: Vec<AccountId32>) -> Vec<AccountId32> {
+    signatories.sort();
+    signatories
+}
+
+impl<S: Signer, Client: RpcClient, N: HasMultisig> Api<'_, S, Client, N> {
+    /// Immediately dispatch a multi-signature call using a single approval
+    /// from the signer.
+    ///
+    /// `other_signatories` must not contain the signer, they are sorted
+    /// before encoding.
+    pub fn multisig_as_multi_threshold_1<Call: Encode + Clone>(
+        &self,
+        other_signatories: Vec<AccountId32>,
+        call: Call,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedMultisigAsMultiThreshold1<Call>>> {
+        let call = (
+            self.call_index([N::MULTISIG_PALLET_IDX, N::MULTISIG_AS_MULTI_THRESHOLD_1])?,
+            sorted(other_signatories),
+            call,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Register approval for a dispatch to be made from a deterministic
+    /// composite account if approved by a total of `threshold` of the
+    /// signatories, dispatching it if this is the final approval.
+    ///
+    /// `maybe_timepoint` must be `None` for the first approval and the
+    /// `when` of the open [`Multisig`] otherwise.
+    pub fn multisig_as_multi<Call: Encode + Clone>(
+        &self,
+        threshold: u16,
+        other_signatories: Vec<AccountId32>,
+        maybe_timepoint: Option<Timepoint>,
+        call: Call,
+        max_weight: Weight,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedMultisigAsMulti<Call>>> {
+        let call = (
+            self.call_index([N::MULTISIG_PALLET_IDX, N::MULTISIG_AS_MULTI])?,
+            threshold,
+            sorted(other_signatories),
+            maybe_timepoint,
+            call,
+            max_weight,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Register approval for a dispatch identified by its [`call_hash`]
+    /// without dispatching it.
+    pub fn multisig_approve_as_multi(
+        &self,
+        threshold: u16,
+        other_signatories: Vec<AccountId32>,
+        maybe_timepoint: Option<Timepoint>,
+        call_hash: [u8; 32],
+        max_weight: Weight,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedMultisigApproveAsMulti>> {
+        let call = (
+            self.call_index([N::MULTISIG_PALLET_IDX, N::MULTISIG_APPROVE_AS_MULTI])?,
+            threshold,
+            sorted(other_signatories),
+            maybe_timepoint,
+            call_hash,
+            max_weight,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Cancel a pre-existing, ongoing multisig transaction. The deposit is
+    /// returned to the depositor, who must be the signer.
+    pub fn multisig_cancel_as_multi(
+        &self,
+        threshold: u16,
+        other_signatories: Vec<AccountId32>,
+        timepoint: Timepoint,
+        call_hash: [u8; 32],
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedMultisigCancelAsMulti>> {
+        let call = (
+            self.call_index([N::MULTISIG_PALLET_IDX, N::MULTISIG_CANCEL_AS_MULTI])?,
+            threshold,
+            sorted(other_signatories),
+            timepoint,
+            call_hash,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Returns the open multisig operation of `multisig_account` for
+    /// `call_hash`.
+    pub fn multisig<A: Into<AccountId32>>(
+        &self,
+        multisig_account: A,
+        call_hash: [u8; 32],
+        at_block: Option<H256>,
+    ) -> Result<Option<Multisig>> {
+        let storage_key = storage_multisig_multisigs(multisig_account.into().as_ref(), &call_hash);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns all open multisig operations of `multisig_account` keyed by
+    /// their call hash.
+    pub fn multisigs<A: Into<AccountId32>>(
+        &self,
+        multisig_account: A,
+        at_block: Option<H256>,
+    ) -> Result<Vec<([u8; 32], Multisig)>> {
+        let prefix = storage_multisig_multisigs_prefix(multisig_account.into().as_ref());
+        let mut multisigs = Vec::new();
+        for key in self.storage_keys(prefix, at_block)? {
+            // the call hash is the raw tail of its `Blake2_128Concat` key
+            let call_hash: [u8; 32] = key.0[key.0.len() - 32..].try_into().expect("32 byte slice");
+            let json_req = state_get_storage(key, at_block);
+            if let Some(multisig) = self.client.post(json_req)?.decode_into()? {
+                multisigs.push((call_hash, multisig));
+            }
+        }
+        Ok(multisigs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ss58::Ss58Codec;
+
+    fn account(s: &str) -> AccountId32 {
+        AccountId32::from_ss58check_with_version(s).unwrap().0
+    }
+
+    #[test]
+    fn multi_account_id_2_of_3() {
+        let alice = account("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+        let bob = account("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty");
+        let charlie = account("5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y");
+        let expected = "5DjYJStmdZ2rcqXbXGX7TW85JsrW6uG4y9MUcLq2BoPMpRA7";
+        let got = multi_account_id(&[alice.clone(), bob.clone(), charlie.clone()], 2);
+        assert_eq!(got.to_ss58check(), expected);
+        // signatory order does not matter
+        let got = multi_account_id(&[charlie, alice, bob], 2);
+        assert_eq!(got.to_ss58check(), expected);
+    }
+
+    #[test]
+    fn weight_encode() {
+        let weight = Weight::from_parts(1_000_000_000, 65536);
+        assert_eq!(hex::encode(weight.encode()), "02286bee02000400");
+    }
+}
@@ -0,0 +1,389 @@
+use parity_scale_codec::{Compact, Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasNominationPools;
+use crate::pallets::staking::EraIndex;
+use crate::pallets::storage::{
+    storage_nomination_pools_bonded_pools, storage_nomination_pools_pool_members,
+    storage_nomination_pools_reward_pools,
+};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, GenericAddress, Perbill, UncheckedExtrinsic, H256};
+
+pub type PoolId = u32;
+
+pub type ComposedNominationPoolsJoin = (CallIndex, Compact<Balance>, PoolId);
+pub type ComposedNominationPoolsBondExtra = (CallIndex, BondExtra);
+pub type ComposedNominationPoolsClaimPayout = CallIndex;
+pub type ComposedNominationPoolsUnbond = (CallIndex, GenericAddress, Compact<Balance>);
+pub type ComposedNominationPoolsPoolWithdrawUnbonded = (CallIndex, PoolId, u32);
+pub type ComposedNominationPoolsWithdrawUnbonded = (CallIndex, GenericAddress, u32);
+pub type ComposedNominationPoolsSetClaimPermission = (CallIndex, ClaimPermission);
+
+/// `PalletId` of the nomination pools pallet on Polkadot, Kusama and Westend
+pub const NOMINATION_POOLS_PALLET_ID: [u8; 8] = *b"py/nopls";
+
+/// A way to bond extra funds to a pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum BondExtra {
+    /// Take from the free balance.
+    FreeBalance(Balance),
+    /// Take the entire amount from the accumulated rewards.
+    Rewards,
+}
+
+/// The permission a pool member can set for other accounts to claim rewards
+/// on their behalf.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum ClaimPermission {
+    /// Only the pool member themself can claim their rewards.
+    Permissioned,
+    /// Anyone can compound rewards on a pool member's behalf.
+    PermissionlessCompound,
+    /// Anyone can withdraw rewards on a pool member's behalf.
+    PermissionlessWithdraw,
+    /// Anyone can withdraw and compound rewards on a pool member's behalf.
+    PermissionlessAll,
+}
+
+/// A member in a pool.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/nomination-pools/src/lib.rs
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct PoolMember {
+    /// The identifier of the pool to which `who` belongs.
+    pub pool_id: PoolId,
+    /// The quantity of points this member has in the bonded pool or in a sub
+    /// pool if `Self::unbonding_era` is some.
+    pub points: Balance,
+    /// The reward counter at the time of this member's last payout claim, a
+    /// `FixedU128` with 18 decimals.
+    pub last_recorded_reward_counter: u128,
+    /// The eras in which this member is unbonding, mapped from era index to
+    /// the number of points scheduled to unbond in the given era.
+    pub unbonding_eras: Vec<(EraIndex, Balance)>,
+}
+
+impl PoolMember {
+    /// Total points that are unbonding
+    pub fn unbonding_points(&self) -> Balance {
+        self.unbonding_eras.iter().map(|(_, points)| points).sum()
+    }
+}
+
+/// A pool's possible states.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum PoolState {
+    /// The pool is open to be joined, and is working normally.
+    Open,
+    /// The pool is blocked. No one else can join.
+    Blocked,
+    /// The pool is in the process of being destroyed.
+    Destroying,
+}
+
+/// Pool administration roles.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct PoolRoles {
+    /// Creates the pool and is the initial member. They can only leave the
+    /// pool once all other members have left.
+    pub depositor: AccountId32,
+    /// Can change the nominator, bouncer, or itself and can perform any of
+    /// the actions the nominator or bouncer can.
+    pub root: Option<AccountId32>,
+    /// Can select which validators the pool nominates.
+    pub nominator: Option<AccountId32>,
+    /// Can change the pools state and kick members if the pool is blocked.
+    pub bouncer: Option<AccountId32>,
+}
+
+/// Pool commission change rate preferences.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct CommissionChangeRate {
+    /// The maximum amount the commission can be updated by per `min_delay`
+    /// period.
+    pub max_increase: Perbill,
+    /// How often an update can take place.
+    pub min_delay: u32,
+}
+
+/// Who can claim the commission of a pool.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum CommissionClaimPermission {
+    Permissionless,
+    Account(AccountId32),
+}
+
+/// Pool commission.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Commission {
+    /// Optional commission rate of the pool along with the account commission
+    /// is paid to.
+    pub current: Option<(Perbill, AccountId32)>,
+    /// Optional maximum commission that can be set by the pool `root`.
+    pub max: Option<Perbill>,
+    /// Optional configuration around how often commission can be updated, and
+    /// when the last commission update took place.
+    pub change_rate: Option<CommissionChangeRate>,
+    /// The block from where throttling should be checked from.
+    pub throttle_from: Option<u32>,
+    /// Whether commission can be claimed permissionlessly, or whether an
+    /// account can claim commission.
+    pub claim_permission: Option<CommissionClaimPermission>,
+}
+
+/// Pool permissions and state.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct BondedPoolInner {
+    /// The commission rate of the pool.
+    pub commission: Commission,
+    /// Count of members that belong to the pool.
+    pub member_counter: u32,
+    /// Total points of all the members in the pool who are actively bonded.
+    pub points: Balance,
+    /// See [`PoolRoles`].
+    pub roles: PoolRoles,
+    /// The current state of the pool.
+    pub state: PoolState,
+}
+
+/// A reward pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct RewardPool {
+    /// The last recorded value of the reward counter, a `FixedU128` with 18
+    /// decimals.
+    pub last_recorded_reward_counter: u128,
+    /// The last recorded total payouts of the reward pool.
+    pub last_recorded_total_payouts: Balance,
+    /// Total amount that this pool has paid out so far to the members.
+    pub total_rewards_claimed: Balance,
+    /// The amount of commission pending to be claimed.
+    pub total_commission_pending: Balance,
+    /// The amount of commission that has been claimed.
+    pub total_commission_claimed: Balance,
+}
+
+/// The type of account being created.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum PoolAccountType {
+    Bonded,
+    Reward,
+}
+
+/// Derive the account of a pool the same way `PalletId::into_sub_account_truncating`
+/// does: `b"modl" ++ pallet_id ++ (account_type, pool_id)`, zero padded to 32
+/// bytes.
+pub fn pool_account(
+    pallet_id: [u8; 8],
+    account_type: PoolAccountType,
+    pool_id: PoolId,
+) -> AccountId32 {
+    let mut account = [0u8; 32];
+    let encoded = (b"modl", pallet_id, account_type, pool_id).encode();
+    account[..encoded.len()].copy_from_slice(&encoded);
+    account.into()
+}
+
+/// The account that bonds the funds of pool `pool_id`
+pub fn pool_bonded_account(pool_id: PoolId) -> AccountId32 {
+    pool_account(NOMINATION_POOLS_PALLET_ID, PoolAccountType::Bonded, pool_id)
+}
+
+/// The account that holds the rewards of pool `pool_id`
+pub fn pool_reward_account(pool_id: PoolId) -> AccountId32 {
+    pool_account(NOMINATION_POOLS_PALLET_ID, PoolAccountType::Reward, pool_id)
+}
+
+impl<S: Signer, Client: RpcClient, N: HasNominationPools> Api<'_, S, Client, N> {
+    /// Stake funds with a pool
+    pub fn nomination_pools_join(
+        &self,
+        amount: Balance,
+        pool_id: PoolId,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedNominationPoolsJoin>> {
+        let call = (
+            self.call_index([N::NOMINATION_POOLS_PALLET_IDX, N::NOMINATION_POOLS_JOIN])?,
+            Compact(amount),
+            pool_id,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Bond `extra` more funds from the signer into the pool it belongs to
+    pub fn nomination_pools_bond_extra(
+        &self,
+        extra: BondExtra,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedNominationPoolsBondExtra>> {
+        let call = (
+            self.call_index([
+                N::NOMINATION_POOLS_PALLET_IDX,
+                N::NOMINATION_POOLS_BOND_EXTRA,
+            ])?,
+            extra,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Pay out the pending rewards of the signer to its free balance
+    pub fn nomination_pools_claim_payout(
+        &self,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedNominationPoolsClaimPayout>> {
+        self._create_xt(
+            self.call_index([
+                N::NOMINATION_POOLS_PALLET_IDX,
+                N::NOMINATION_POOLS_CLAIM_PAYOUT,
+            ])?,
+            nonce,
+        )
+    }
+
+    /// Unbond up to `unbonding_points` of the `member_account`'s funds from
+    /// the pool
+    pub fn nomination_pools_unbond(
+        &self,
+        member_account: GenericAddress,
+        unbonding_points: Balance,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedNominationPoolsUnbond>> {
+        let call = (
+            self.call_index([N::NOMINATION_POOLS_PALLET_IDX, N::NOMINATION_POOLS_UNBOND])?,
+            member_account,
+            Compact(unbonding_points),
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Call `withdraw_unbonded` for the pool's bonded account, so that its
+    /// unlocked chunks can be withdrawn by the members
+    pub fn nomination_pools_pool_withdraw_unbonded(
+        &self,
+        pool_id: PoolId,
+        num_slashing_spans: u32,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedNominationPoolsPoolWithdrawUnbonded>> {
+        let call = (
+            self.call_index([
+                N::NOMINATION_POOLS_PALLET_IDX,
+                N::NOMINATION_POOLS_POOL_WITHDRAW_UNBONDED,
+            ])?,
+            pool_id,
+            num_slashing_spans,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Withdraw unbonded funds from `member_account`
+    pub fn nomination_pools_withdraw_unbonded(
+        &self,
+        member_account: GenericAddress,
+        num_slashing_spans: u32,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedNominationPoolsWithdrawUnbonded>> {
+        let call = (
+            self.call_index([
+                N::NOMINATION_POOLS_PALLET_IDX,
+                N::NOMINATION_POOLS_WITHDRAW_UNBONDED,
+            ])?,
+            member_account,
+            num_slashing_spans,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Allow others to claim the signer's pending rewards
+    pub fn nomination_pools_set_claim_permission(
+        &self,
+        permission: ClaimPermission,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedNominationPoolsSetClaimPermission>> {
+        let call = (
+            self.call_index([
+                N::NOMINATION_POOLS_PALLET_IDX,
+                N::NOMINATION_POOLS_SET_CLAIM_PERMISSION,
+            ])?,
+            permission,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasNominationPools> Api<'_, S, Client, N> {
+    /// Returns the pool membership of `member`
+    pub fn nomination_pools_pool_members<A: Into<AccountId32>>(
+        &self,
+        member: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<PoolMember>> {
+        let storage_key = storage_nomination_pools_pool_members(member.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the bonded pool `pool_id`
+    pub fn nomination_pools_bonded_pools(
+        &self,
+        pool_id: PoolId,
+        at_block: Option<H256>,
+    ) -> Result<Option<BondedPoolInner>> {
+        let storage_key = storage_nomination_pools_bonded_pools(pool_id);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the reward pool of `pool_id`
+    pub fn nomination_pools_reward_pools(
+        &self,
+        pool_id: PoolId,
+        at_block: Option<H256>,
+    ) -> Result<Option<RewardPool>> {
+        let storage_key = storage_nomination_pools_reward_pools(pool_id);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_accounts() {
+        // b"modl" ++ b"py/nopls" ++ account type ++ pool id, zero padded
+        let bonded = pool_bonded_account(1);
+        assert_eq!(
+            hex::encode(<AccountId32 as AsRef<[u8]>>::as_ref(&bonded)),
+            "6d6f646c70792f6e6f706c730001000000000000000000000000000000000000"
+        );
+        let reward = pool_reward_account(1);
+        assert_eq!(
+            hex::encode(<AccountId32 as AsRef<[u8]>>::as_ref(&reward)),
+            "6d6f646c70792f6e6f706c730101000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn pool_member_decode() {
+        let encoded = hex::decode(concat!(
+            // pool_id: 12
+            "0c000000",
+            // points: 10 DOT
+            "00e87648170000000000000000000000",
+            // last_recorded_reward_counter
+            "00000000000000000000000000000000",
+            // unbonding_eras: {1500: 1 DOT}
+            "04",
+            "dc050000",
+            "00e40b54020000000000000000000000",
+        ))
+        .unwrap();
+        let member = PoolMember::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(member.pool_id, 12);
+        assert_eq!(member.points, 100_000_000_000);
+        assert_eq!(member.unbonding_eras, vec![(1500, 10_000_000_000)]);
+        assert_eq!(member.unbonding_points(), 10_000_000_000);
+    }
+}
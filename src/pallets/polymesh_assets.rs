@@ -0,0 +1,322 @@
+use std::str::FromStr;
+
+use parity_scale_codec::{Decode, Encode};
+use sp_core::hashing::blake2_128;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasPolymeshAssets;
+use crate::pallets::polymesh_identity::IdentityId;
+use crate::pallets::storage::{
+    storage_asset_balance_of, storage_portfolio_portfolio_asset_balances,
+    storage_settlement_instruction_details,
+};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, UncheckedExtrinsic, H256};
+
+pub type VenueId = u64;
+pub type InstructionId = u64;
+pub type PortfolioNumber = u64;
+pub type NFTId = u64;
+/// Milliseconds since the Unix epoch
+pub type Moment = u64;
+
+pub type ComposedSettlementAddAndAffirmInstruction = (
+    CallIndex,
+    Option<VenueId>,
+    SettlementType,
+    Option<Moment>,
+    Option<Moment>,
+    Vec<Leg>,
+    Vec<PortfolioId>,
+    Option<Memo>,
+);
+pub type ComposedSettlementAffirmInstruction = (CallIndex, InstructionId, Vec<PortfolioId>);
+pub type ComposedPortfolioMovePortfolioFunds = (CallIndex, PortfolioId, PortfolioId, Vec<Fund>);
+
+/// Ticker symbol of an asset, upper case and padded with zeros to 12 bytes
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Encode, Decode)]
+pub struct Ticker(pub [u8; 12]);
+
+impl FromStr for Ticker {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.is_empty() || s.len() > 12 {
+            return Err("Invalid Ticker. Expecting 1 to 12 bytes");
+        }
+        let mut ticker = [0; 12];
+        ticker[..s.len()].copy_from_slice(s.to_ascii_uppercase().as_bytes());
+        Ok(Ticker(ticker))
+    }
+}
+
+/// Identifier of an asset, which replaced tickers as asset keys in Polymesh 7
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Encode, Decode)]
+pub struct AssetId(pub [u8; 16]);
+
+impl From<Ticker> for AssetId {
+    /// The id assets created with a ticker before Polymesh 7 were migrated
+    /// to
+    fn from(ticker: Ticker) -> Self {
+        AssetId(blake2_128(&(b"legacy_ticker", ticker).encode()))
+    }
+}
+
+/// Describes what kind of portfolio it is
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub enum PortfolioKind {
+    /// The default portfolio of a DID.
+    #[default]
+    Default,
+    /// A user-defined portfolio of a DID.
+    User(PortfolioNumber),
+}
+
+/// A portfolio of a DID
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct PortfolioId {
+    pub did: IdentityId,
+    pub kind: PortfolioKind,
+}
+
+impl PortfolioId {
+    pub fn default_portfolio(did: IdentityId) -> Self {
+        PortfolioId {
+            did,
+            kind: PortfolioKind::Default,
+        }
+    }
+
+    pub fn user_portfolio(did: IdentityId, num: PortfolioNumber) -> Self {
+        PortfolioId {
+            did,
+            kind: PortfolioKind::User(num),
+        }
+    }
+}
+
+/// A free-form 32 byte memo
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct Memo(pub [u8; 32]);
+
+/// A set of NFTs of one asset
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct NFTs {
+    pub asset_id: AssetId,
+    pub ids: Vec<NFTId>,
+}
+
+/// Type of settlement
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum SettlementType {
+    /// Instruction should be settled in the next block as soon as all
+    /// affirmations are received.
+    SettleOnAffirmation,
+    /// Instruction should be settled on a particular block.
+    SettleOnBlock(u32),
+    /// Instruction must be settled manually on or after a particular block.
+    SettleManual(u32),
+}
+
+/// A leg of an instruction
+/// https://github.com/PolymeshAssociation/Polymesh/blob/develop/pallets/common/src/traits/settlement.rs
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum Leg {
+    /// Fungible tokens moved between two portfolios
+    Fungible {
+        sender: PortfolioId,
+        receiver: PortfolioId,
+        asset_id: AssetId,
+        amount: Balance,
+    },
+    /// NFTs moved between two portfolios
+    NonFungible {
+        sender: PortfolioId,
+        receiver: PortfolioId,
+        nfts: NFTs,
+    },
+    /// An asset transferred off chain, only affirmed on chain
+    OffChain {
+        sender_identity: IdentityId,
+        receiver_identity: IdentityId,
+        ticker: Ticker,
+        amount: Balance,
+    },
+}
+
+/// Describes what is moved between portfolios
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum FundDescription {
+    Fungible { asset_id: AssetId, amount: Balance },
+    NonFungible(NFTs),
+}
+
+/// Funds moved between portfolios
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Fund {
+    pub description: FundDescription,
+    pub memo: Option<Memo>,
+}
+
+/// Details about an instruction
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct Instruction {
+    /// Unique instruction id. It is an auto incrementing number
+    pub instruction_id: InstructionId,
+    /// Id of the venue this instruction belongs to
+    pub venue_id: Option<VenueId>,
+    /// Type of settlement used for this instruction
+    pub settlement_type: SettlementType,
+    /// Date at which this instruction was created
+    pub created_at: Option<Moment>,
+    /// Date from which this instruction is valid
+    pub trade_date: Option<Moment>,
+    /// Date after which the instruction should be settled (not enforced)
+    pub value_date: Option<Moment>,
+}
+
+impl<S: Signer, Client: RpcClient, N: HasPolymeshAssets> Api<'_, S, Client, N> {
+    /// Adds an instruction moving `legs` and affirms it for `portfolios` of
+    /// the signer's identity
+    #[allow(clippy::too_many_arguments)]
+    pub fn settlement_add_and_affirm_instruction(
+        &self,
+        venue_id: Option<VenueId>,
+        settlement_type: SettlementType,
+        trade_date: Option<Moment>,
+        value_date: Option<Moment>,
+        legs: Vec<Leg>,
+        portfolios: Vec<PortfolioId>,
+        instruction_memo: Option<Memo>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedSettlementAddAndAffirmInstruction>> {
+        let call = (
+            self.call_index([
+                N::SETTLEMENT_PALLET_IDX,
+                N::SETTLEMENT_ADD_AND_AFFIRM_INSTRUCTION,
+            ])?,
+            venue_id,
+            settlement_type,
+            trade_date,
+            value_date,
+            legs,
+            portfolios,
+            instruction_memo,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Affirms instruction `id` for `portfolios` of the signer's identity
+    pub fn settlement_affirm_instruction(
+        &self,
+        id: InstructionId,
+        portfolios: Vec<PortfolioId>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedSettlementAffirmInstruction>> {
+        let call = (
+            self.call_index([N::SETTLEMENT_PALLET_IDX, N::SETTLEMENT_AFFIRM_INSTRUCTION])?,
+            id,
+            portfolios,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Moves `funds` between two portfolios of the signer's identity
+    pub fn portfolio_move_portfolio_funds(
+        &self,
+        from: PortfolioId,
+        to: PortfolioId,
+        funds: Vec<Fund>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedPortfolioMovePortfolioFunds>> {
+        let call = (
+            self.call_index([N::PORTFOLIO_PALLET_IDX, N::PORTFOLIO_MOVE_PORTFOLIO_FUNDS])?,
+            from,
+            to,
+            funds,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasPolymeshAssets> Api<'_, S, Client, N> {
+    /// Returns the balance of `asset_id` held by `did` across its portfolios
+    pub fn asset_balance_of(
+        &self,
+        asset_id: AssetId,
+        did: IdentityId,
+        at_block: Option<H256>,
+    ) -> Result<Balance> {
+        let storage_key = storage_asset_balance_of(&asset_id.0, &did.0);
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the balance of `asset_id` in `portfolio`
+    pub fn portfolio_portfolio_asset_balances(
+        &self,
+        portfolio: PortfolioId,
+        asset_id: AssetId,
+        at_block: Option<H256>,
+    ) -> Result<Balance> {
+        let storage_key =
+            storage_portfolio_portfolio_asset_balances(&portfolio.encode(), &asset_id.0);
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the details of instruction `id`
+    pub fn settlement_instruction_details(
+        &self,
+        id: InstructionId,
+        at_block: Option<H256>,
+    ) -> Result<Option<Instruction>> {
+        let storage_key = storage_settlement_instruction_details(id);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticker_encode() {
+        let ticker = Ticker::from_str("acme").unwrap();
+        assert_eq!(hex::encode(ticker.encode()), "41434d450000000000000000");
+        assert!(Ticker::from_str("").is_err());
+        assert!(Ticker::from_str("THIRTEENBYTES").is_err());
+    }
+
+    #[test]
+    fn leg_encode() {
+        let did = IdentityId([1; 32]);
+        let leg = Leg::Fungible {
+            sender: PortfolioId::default_portfolio(did),
+            receiver: PortfolioId::user_portfolio(did, 2),
+            asset_id: AssetId([3; 16]),
+            amount: 5,
+        };
+        let expected = [
+            [0].as_slice(),
+            &[1; 32],
+            &[0],
+            &[1; 32],
+            &[1, 2, 0, 0, 0, 0, 0, 0, 0],
+            &[3; 16],
+            &5u128.to_le_bytes(),
+        ]
+        .concat();
+        assert_eq!(leg.encode(), expected);
+    }
+}
@@ -0,0 +1,234 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasPolymeshIdentity;
+use crate::pallets::storage::{
+    storage_identity_authorizations_given_prefix, storage_identity_authorizations_prefix,
+    storage_identity_key_records,
+};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{UncheckedExtrinsic, H256};
+
+pub type AuthId = u64;
+
+pub type ComposedJoinIdentity = (CallIndex, AuthId);
+pub type ComposedIdentityAcceptPrimaryKey = (CallIndex, AuthId, Option<AuthId>);
+pub type ComposedIdentityRemoveAuthorization = (CallIndex, Signatory, AuthId, bool);
+
+/// A Polymesh decentralized identity (DID)
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Encode, Decode)]
+pub struct IdentityId(pub [u8; 32]);
+
+/// Either a DID or a key an authorization is given to
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum Signatory {
+    Identity(IdentityId),
+    Account(AccountId32),
+}
+
+/// The kind of an authorization, the leading variant of its
+/// `AuthorizationData`. The data following it depends on the runtime version
+/// and is not decoded.
+/// https://github.com/PolymeshAssociation/Polymesh/blob/develop/primitives/src/authorization.rs
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum AuthorizationType {
+    AttestPrimaryKeyRotation,
+    RotatePrimaryKey,
+    TransferTicker,
+    AddMultiSigSigner,
+    TransferAssetOwnership,
+    JoinIdentity,
+    PortfolioCustody,
+    BecomeAgent,
+    AddRelayerPayingKey,
+    RotatePrimaryKeyToSecondary,
+}
+
+/// An authorization given to a signatory
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PolymeshAuthorization {
+    pub auth_id: AuthId,
+    pub auth_type: AuthorizationType,
+}
+
+/// The identity a key belongs to. Only the DID is decoded for secondary
+/// keys, not their permissions.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum KeyRecord {
+    PrimaryKey(IdentityId),
+    SecondaryKey(IdentityId),
+    MultiSigSignerKey(AccountId32),
+}
+
+impl KeyRecord {
+    /// The DID of a primary or secondary key
+    pub fn did(&self) -> Option<IdentityId> {
+        match self {
+            KeyRecord::PrimaryKey(did) | KeyRecord::SecondaryKey(did) => Some(*did),
+            KeyRecord::MultiSigSignerKey(_) => None,
+        }
+    }
+}
+
+/// The auth id ending a storage key hashed with `Twox64Concat` or
+/// `Blake2_128Concat`
+fn auth_id_of(key: &[u8]) -> AuthId {
+    AuthId::from_le_bytes(key[key.len() - 8..].try_into().expect("8 bytes"))
+}
+
+impl<S: Signer, Client: RpcClient, N: HasPolymeshIdentity> Api<'_, S, Client, N> {
+    /// Joins the identity that authorized the signer as a secondary key with
+    /// `auth_id`
+    pub fn join_identity_as_key(
+        &self,
+        auth_id: AuthId,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedJoinIdentity>> {
+        let call = (
+            self.call_index([N::IDENTITY_PALLET_IDX, N::IDENTITY_JOIN_AS_KEY])?,
+            auth_id,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Makes the signer the primary key of the identity that authorized it
+    /// with `rotation_auth_id`. A CDD authorization is only needed if the
+    /// identity requires it for primary key rotation.
+    pub fn identity_accept_primary_key(
+        &self,
+        rotation_auth_id: AuthId,
+        optional_cdd_auth_id: Option<AuthId>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIdentityAcceptPrimaryKey>> {
+        let call = (
+            self.call_index([N::IDENTITY_PALLET_IDX, N::IDENTITY_ACCEPT_PRIMARY_KEY])?,
+            rotation_auth_id,
+            optional_cdd_auth_id,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Removes authorization `auth_id` given to `target`. Can be signed by
+    /// the issuer or the target.
+    pub fn identity_remove_authorization(
+        &self,
+        target: Signatory,
+        auth_id: AuthId,
+        auth_issuer_pays: bool,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedIdentityRemoveAuthorization>> {
+        let call = (
+            self.call_index([N::IDENTITY_PALLET_IDX, N::IDENTITY_REMOVE_AUTHORIZATION])?,
+            target,
+            auth_id,
+            auth_issuer_pays,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Returns the authorizations given to the signer's key
+    pub fn identity_signer_authorizations(
+        &self,
+        at_block: Option<H256>,
+    ) -> Result<Vec<PolymeshAuthorization>> {
+        let signer = Signatory::Account(self.signer_account()?);
+        self.identity_authorizations(&signer, at_block)
+    }
+
+    /// Returns the authorizations given by the identity of the signer's key
+    pub fn identity_signer_authorizations_given(
+        &self,
+        at_block: Option<H256>,
+    ) -> Result<Vec<(AuthId, Signatory)>> {
+        let did = self
+            .identity_key_records(self.signer_account()?, at_block)?
+            .and_then(|r| r.did());
+        match did {
+            Some(did) => self.identity_authorizations_given(did, at_block),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Returns the id of the oldest authorization for the signer's key to
+    /// join an identity, ready for `join_identity_as_key`
+    pub fn identity_join_identity_auth_id(&self, at_block: Option<H256>) -> Result<Option<AuthId>> {
+        Ok(self
+            .identity_signer_authorizations(at_block)?
+            .into_iter()
+            .filter(|a| a.auth_type == AuthorizationType::JoinIdentity)
+            .map(|a| a.auth_id)
+            .min())
+    }
+}
+
+impl<S, Client: RpcClient, N: HasPolymeshIdentity> Api<'_, S, Client, N> {
+    /// Returns the identity `key` belongs to
+    pub fn identity_key_records<A: Into<AccountId32>>(
+        &self,
+        key: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<KeyRecord>> {
+        let storage_key = storage_identity_key_records(key.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the authorizations given to `signatory`, including expired
+    /// ones
+    pub fn identity_authorizations(
+        &self,
+        signatory: &Signatory,
+        at_block: Option<H256>,
+    ) -> Result<Vec<PolymeshAuthorization>> {
+        let prefix = storage_identity_authorizations_prefix(&signatory.encode());
+        let keys = self.storage_keys(prefix, at_block)?;
+        Ok(self
+            .storage_values::<AuthorizationType>(&keys, at_block)?
+            .into_iter()
+            .map(|(k, auth_type)| PolymeshAuthorization {
+                auth_id: auth_id_of(&k.0),
+                auth_type,
+            })
+            .collect())
+    }
+
+    /// Returns the authorizations given by `did` and who they were given to
+    pub fn identity_authorizations_given(
+        &self,
+        did: IdentityId,
+        at_block: Option<H256>,
+    ) -> Result<Vec<(AuthId, Signatory)>> {
+        let prefix = storage_identity_authorizations_given_prefix(&did.encode());
+        let keys = self.storage_keys(prefix, at_block)?;
+        Ok(self
+            .storage_values::<Signatory>(&keys, at_block)?
+            .into_iter()
+            .map(|(k, target)| (auth_id_of(&k.0), target))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_record_decode() {
+        let did = IdentityId([3; 32]);
+        // secondary key with trailing permissions of older runtimes
+        let encoded = [[1].as_slice(), &did.0, &[0, 0, 0]].concat();
+        let record = KeyRecord::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(record, KeyRecord::SecondaryKey(did));
+        assert_eq!(record.did(), Some(did));
+    }
+
+    #[test]
+    fn signatory_encode() {
+        let signatory = Signatory::Account(AccountId32::new([1; 32]));
+        assert_eq!(signatory.encode(), [[1].as_slice(), &[1; 32]].concat());
+        let key = [[0xab; 40].as_slice(), &42u64.to_le_bytes()].concat();
+        assert_eq!(auth_id_of(&key), 42);
+    }
+}
@@ -5,7 +5,7 @@ use parity_scale_codec::{Decode, Encode};
 use sp_core::crypto::AccountId32;
 
 use crate::client::{Api, Result, Signer};
-use crate::network::SubstrateNetwork;
+use crate::network::HasProxy;
 use crate::pallets::storage::storage_proxy_proxies;
 use crate::pallets::CallIndex;
 use crate::rpc::{state_get_storage, RpcClient};
@@ -66,6 +66,72 @@ impl FromStr for WestendProxyType {
     }
 }
 
+/// Proxy types of the People chains
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum PeopleProxyType {
+    Any,
+    NonTransfer,
+    CancelProxy,
+    Identity,
+    IdentityJudgement,
+    Collator,
+}
+
+impl FromStr for PeopleProxyType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ptype = match s {
+            "Any" => PeopleProxyType::Any,
+            "NonTransfer" => PeopleProxyType::NonTransfer,
+            "CancelProxy" => PeopleProxyType::CancelProxy,
+            "Identity" => PeopleProxyType::Identity,
+            "IdentityJudgement" => PeopleProxyType::IdentityJudgement,
+            "Collator" => PeopleProxyType::Collator,
+            _ => {
+                return Err("Invalid PeopleProxyType. Expecting 'Any', 'NonTransfer', \
+                     'CancelProxy', 'Identity', 'IdentityJudgement' or 'Collator'")
+            }
+        };
+        Ok(ptype)
+    }
+}
+
+/// Proxy types of the Asset Hub chains
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum AssetHubProxyType {
+    Any,
+    NonTransfer,
+    CancelProxy,
+    Assets,
+    AssetOwner,
+    AssetManager,
+    Collator,
+}
+
+impl FromStr for AssetHubProxyType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ptype = match s {
+            "Any" => AssetHubProxyType::Any,
+            "NonTransfer" => AssetHubProxyType::NonTransfer,
+            "CancelProxy" => AssetHubProxyType::CancelProxy,
+            "Assets" => AssetHubProxyType::Assets,
+            "AssetOwner" => AssetHubProxyType::AssetOwner,
+            "AssetManager" => AssetHubProxyType::AssetManager,
+            "Collator" => AssetHubProxyType::Collator,
+            _ => {
+                return Err(
+                    "Invalid AssetHubProxyType. Expecting 'Any', 'NonTransfer', \
+                     'CancelProxy', 'Assets', 'AssetOwner', 'AssetManager' or 'Collator'",
+                )
+            }
+        };
+        Ok(ptype)
+    }
+}
+
 /// The parameters under which a particular account has a proxy relationship
 /// with some other account.
 /// https://github.com/paritytech/substrate/blob/master/frame/proxy/src/lib.rs
@@ -82,7 +148,7 @@ pub struct ProxyDefinition<AccountId, ProxyType, BlockNumber> {
 }
 
 #[allow(clippy::type_complexity)]
-impl<S: Signer, C: RpcClient, N: SubstrateNetwork> Api<'_, S, C, N> {
+impl<S: Signer, C: RpcClient, N: HasProxy> Api<'_, S, C, N> {
     /// Register a proxy account for the sender that is able to make calls on
     /// its behalf.
     ///
@@ -96,7 +162,7 @@ impl<S: Signer, C: RpcClient, N: SubstrateNetwork> Api<'_, S, C, N> {
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<(CallIndex, N::ProxyDelegateType, N::ProxyTypeType, u32)>> {
         let call = (
-            [N::PROXY_PALLET_IDX, N::PROXY_ADD_PROXY],
+            self.call_index([N::PROXY_PALLET_IDX, N::PROXY_ADD_PROXY])?,
             delegate,
             proxy_type,
             delay,
@@ -117,7 +183,7 @@ impl<S: Signer, C: RpcClient, N: SubstrateNetwork> Api<'_, S, C, N> {
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<(CallIndex, N::ProxyDelegateType, N::ProxyTypeType, u32)>> {
         let call = (
-            [N::PROXY_PALLET_IDX, N::PROXY_REMOVE_PROXY],
+            self.call_index([N::PROXY_PALLET_IDX, N::PROXY_REMOVE_PROXY])?,
             delegate,
             proxy_type,
             delay,
@@ -130,7 +196,10 @@ impl<S: Signer, C: RpcClient, N: SubstrateNetwork> Api<'_, S, C, N> {
         &self,
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedProxyRemoveProxies>> {
-        self._create_xt([N::PROXY_PALLET_IDX, N::PROXY_REMOVE_PROXIES], nonce)
+        self._create_xt(
+            self.call_index([N::PROXY_PALLET_IDX, N::PROXY_REMOVE_PROXIES])?,
+            nonce,
+        )
     }
 
     /// Returns proxies set for current account.
@@ -0,0 +1,488 @@
+use parity_scale_codec::{Decode, Encode, Error, Input, Output};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result};
+use crate::network::HasReferenda;
+use crate::pallets::conviction_voting::{PollIndex, Tally};
+use crate::pallets::storage::{storage_referenda_referendum_info_for, storage_value_key};
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, H256};
+
+pub type ReferendumIndex = PollIndex;
+pub type TrackId = u16;
+
+/// A referendum track
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Track {
+    pub id: TrackId,
+    pub name: &'static str,
+}
+
+/// The tracks of the Polkadot runtime
+pub const POLKADOT_TRACKS: &[Track] = &[
+    Track {
+        id: 0,
+        name: "root",
+    },
+    Track {
+        id: 1,
+        name: "whitelisted_caller",
+    },
+    Track {
+        id: 2,
+        name: "wish_for_change",
+    },
+    Track {
+        id: 10,
+        name: "staking_admin",
+    },
+    Track {
+        id: 11,
+        name: "treasurer",
+    },
+    Track {
+        id: 12,
+        name: "lease_admin",
+    },
+    Track {
+        id: 13,
+        name: "fellowship_admin",
+    },
+    Track {
+        id: 14,
+        name: "general_admin",
+    },
+    Track {
+        id: 15,
+        name: "auction_admin",
+    },
+    Track {
+        id: 20,
+        name: "referendum_canceller",
+    },
+    Track {
+        id: 21,
+        name: "referendum_killer",
+    },
+    Track {
+        id: 30,
+        name: "small_tipper",
+    },
+    Track {
+        id: 31,
+        name: "big_tipper",
+    },
+    Track {
+        id: 32,
+        name: "small_spender",
+    },
+    Track {
+        id: 33,
+        name: "medium_spender",
+    },
+    Track {
+        id: 34,
+        name: "big_spender",
+    },
+];
+
+/// The tracks of the Kusama runtime
+pub const KUSAMA_TRACKS: &[Track] = &[
+    Track {
+        id: 0,
+        name: "root",
+    },
+    Track {
+        id: 1,
+        name: "whitelisted_caller",
+    },
+    Track {
+        id: 2,
+        name: "wish_for_change",
+    },
+    Track {
+        id: 10,
+        name: "staking_admin",
+    },
+    Track {
+        id: 11,
+        name: "treasurer",
+    },
+    Track {
+        id: 12,
+        name: "lease_admin",
+    },
+    Track {
+        id: 13,
+        name: "fellowship_admin",
+    },
+    Track {
+        id: 14,
+        name: "general_admin",
+    },
+    Track {
+        id: 15,
+        name: "auction_admin",
+    },
+    Track {
+        id: 20,
+        name: "referendum_canceller",
+    },
+    Track {
+        id: 21,
+        name: "referendum_killer",
+    },
+    Track {
+        id: 30,
+        name: "small_tipper",
+    },
+    Track {
+        id: 31,
+        name: "big_tipper",
+    },
+    Track {
+        id: 32,
+        name: "small_spender",
+    },
+    Track {
+        id: 33,
+        name: "medium_spender",
+    },
+    Track {
+        id: 34,
+        name: "big_spender",
+    },
+];
+
+/// The tracks of the Westend runtime, which has no `wish_for_change` track
+pub const WESTEND_TRACKS: &[Track] = &[
+    Track {
+        id: 0,
+        name: "root",
+    },
+    Track {
+        id: 1,
+        name: "whitelisted_caller",
+    },
+    Track {
+        id: 10,
+        name: "staking_admin",
+    },
+    Track {
+        id: 11,
+        name: "treasurer",
+    },
+    Track {
+        id: 12,
+        name: "lease_admin",
+    },
+    Track {
+        id: 13,
+        name: "fellowship_admin",
+    },
+    Track {
+        id: 14,
+        name: "general_admin",
+    },
+    Track {
+        id: 15,
+        name: "auction_admin",
+    },
+    Track {
+        id: 20,
+        name: "referendum_canceller",
+    },
+    Track {
+        id: 21,
+        name: "referendum_killer",
+    },
+    Track {
+        id: 30,
+        name: "small_tipper",
+    },
+    Track {
+        id: 31,
+        name: "big_tipper",
+    },
+    Track {
+        id: 32,
+        name: "small_spender",
+    },
+    Track {
+        id: 33,
+        name: "medium_spender",
+    },
+    Track {
+        id: 34,
+        name: "big_spender",
+    },
+];
+
+/// The origin a referendum dispatches its proposal with. Referenda are
+/// either submitted with a system origin (pallet variant 0) or with one of
+/// the runtime's custom governance origins, which encode as their pallet
+/// variant and origin index.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ReferendumOrigin {
+    Root,
+    Signed(AccountId32),
+    None,
+    Custom { pallet: u8, origin: u8 },
+}
+
+impl Encode for ReferendumOrigin {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        match self {
+            ReferendumOrigin::Root => output.write(&[0, 0]),
+            ReferendumOrigin::Signed(who) => {
+                output.write(&[0, 1]);
+                who.encode_to(output);
+            }
+            ReferendumOrigin::None => output.write(&[0, 2]),
+            ReferendumOrigin::Custom { pallet, origin } => output.write(&[*pallet, *origin]),
+        }
+    }
+}
+
+impl Decode for ReferendumOrigin {
+    fn decode<I: Input>(input: &mut I) -> std::result::Result<Self, Error> {
+        let pallet = input.read_byte()?;
+        let origin = input.read_byte()?;
+        Ok(match (pallet, origin) {
+            (0, 0) => ReferendumOrigin::Root,
+            (0, 1) => ReferendumOrigin::Signed(AccountId32::decode(input)?),
+            (0, 2) => ReferendumOrigin::None,
+            (0, _) => return Err("Invalid system origin".into()),
+            (pallet, origin) => ReferendumOrigin::Custom { pallet, origin },
+        })
+    }
+}
+
+/// A proposal call, inline or referred to by its preimage hash
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum BoundedCall {
+    Legacy { hash: H256 },
+    Inline(Vec<u8>),
+    Lookup { hash: H256, len: u32 },
+}
+
+/// When a proposal is enacted
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum DispatchTime {
+    /// At specified block.
+    At(u32),
+    /// After specified number of blocks.
+    After(u32),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Deposit {
+    pub who: AccountId32,
+    pub amount: Balance,
+}
+
+/// Details of a referendum in its deciding period
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct DecidingStatus {
+    /// When this referendum began being "decided". If confirming, then the
+    /// end will actually be delayed until the end of the confirmation period.
+    pub since: u32,
+    /// If `Some`, then the referendum has entered confirmation stage and will
+    /// end at the block number as long as it doesn't lose its approval in the
+    /// meantime.
+    pub confirming: Option<u32>,
+}
+
+/// Info regarding an ongoing referendum.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/referenda/src/types.rs
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct ReferendumStatus {
+    /// The track of this referendum.
+    pub track: TrackId,
+    /// The origin for this referendum.
+    pub origin: ReferendumOrigin,
+    /// The hash of the proposal up for referendum.
+    pub proposal: BoundedCall,
+    /// The time the proposal should be scheduled for enactment.
+    pub enactment: DispatchTime,
+    /// The time of submission. Once `UndecidingTimeout` passes, it may be
+    /// closed by anyone if `deciding` is `None`.
+    pub submitted: u32,
+    /// The deposit reserved for the submission of this referendum.
+    pub submission_deposit: Deposit,
+    /// The deposit reserved for this referendum to be decided.
+    pub decision_deposit: Option<Deposit>,
+    /// The status of a decision being made. If `None`, it has not entered the
+    /// deciding period.
+    pub deciding: Option<DecidingStatus>,
+    /// The current tally of votes in this referendum.
+    pub tally: Tally,
+    /// Whether we have been placed in the queue for being decided or not.
+    pub in_queue: bool,
+    /// The next scheduled wake-up, if `Some`.
+    pub alarm: Option<(u32, (u32, u32))>,
+}
+
+/// Info regarding a referendum, present or past.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum ReferendumInfo {
+    /// Referendum has been submitted and is being voted on.
+    Ongoing(ReferendumStatus),
+    /// Referendum finished with approval. Submission deposit is held.
+    Approved(u32, Option<Deposit>, Option<Deposit>),
+    /// Referendum finished with rejection. Submission deposit is held.
+    Rejected(u32, Option<Deposit>, Option<Deposit>),
+    /// Referendum finished with cancellation. Submission deposit is held.
+    Cancelled(u32, Option<Deposit>, Option<Deposit>),
+    /// Referendum finished and was never decided. Submission deposit is held.
+    TimedOut(u32, Option<Deposit>, Option<Deposit>),
+    /// Referendum finished with a kill.
+    Killed(u32),
+}
+
+impl ReferendumInfo {
+    /// The status of the referendum if it is ongoing
+    pub fn ongoing(&self) -> Option<&ReferendumStatus> {
+        match self {
+            ReferendumInfo::Ongoing(status) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// The block the referendum ended at, `None` if it is ongoing
+    pub fn ended_at(&self) -> Option<u32> {
+        match self {
+            ReferendumInfo::Ongoing(_) => None,
+            ReferendumInfo::Approved(end, ..)
+            | ReferendumInfo::Rejected(end, ..)
+            | ReferendumInfo::Cancelled(end, ..)
+            | ReferendumInfo::TimedOut(end, ..)
+            | ReferendumInfo::Killed(end) => Some(*end),
+        }
+    }
+}
+
+/// Looks up track `id` in `tracks`
+pub fn track(tracks: &[Track], id: TrackId) -> Option<&Track> {
+    tracks.iter().find(|t| t.id == id)
+}
+
+impl<S, Client: RpcClient, N: HasReferenda> Api<'_, S, Client, N> {
+    /// Returns the number of referenda submitted so far, which is also the
+    /// index of the next one
+    pub fn referenda_referendum_count(&self, at_block: Option<H256>) -> Result<ReferendumIndex> {
+        let storage_key = storage_value_key("Referenda", "ReferendumCount");
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns referendum `index` as it was at `at_block`, use
+    /// [`Api::block_hash`] to look it up at a past block number
+    pub fn referenda_referendum_info_for(
+        &self,
+        index: ReferendumIndex,
+        at_block: Option<H256>,
+    ) -> Result<Option<ReferendumInfo>> {
+        let storage_key = storage_referenda_referendum_info_for(index);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns all referenda still in storage, by index
+    pub fn referenda_referenda(
+        &self,
+        at_block: Option<H256>,
+    ) -> Result<Vec<(ReferendumIndex, ReferendumInfo)>> {
+        let prefix = storage_value_key("Referenda", "ReferendumInfoFor");
+        let keys = self.storage_keys(prefix, at_block)?;
+        let mut referenda: Vec<(ReferendumIndex, ReferendumInfo)> = self
+            .storage_values(&keys, at_block)?
+            .into_iter()
+            .map(|(k, info)| {
+                // the index is the raw tail of its `Blake2_128Concat` key
+                let index = k.0[k.0.len() - 4..].try_into().expect("4 bytes");
+                (ReferendumIndex::from_le_bytes(index), info)
+            })
+            .collect();
+        referenda.sort_by_key(|(index, _)| *index);
+        Ok(referenda)
+    }
+
+    /// Returns the referenda being voted on, by index
+    pub fn referenda_ongoing(
+        &self,
+        at_block: Option<H256>,
+    ) -> Result<Vec<(ReferendumIndex, ReferendumStatus)>> {
+        Ok(self
+            .referenda_referenda(at_block)?
+            .into_iter()
+            .filter_map(|(index, info)| match info {
+                ReferendumInfo::Ongoing(status) => Some((index, status)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Looks up the track of the network with `id`
+    pub fn referenda_track(&self, id: TrackId) -> Option<&'static Track> {
+        track(N::REFERENDA_TRACKS, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perbill;
+
+    #[test]
+    fn referendum_info_decode() {
+        let who = AccountId32::new([1; 32]);
+        let status = ReferendumStatus {
+            track: 33,
+            origin: ReferendumOrigin::Custom {
+                pallet: 22,
+                origin: 11,
+            },
+            proposal: BoundedCall::Lookup {
+                hash: H256::repeat_byte(2),
+                len: 100,
+            },
+            enactment: DispatchTime::After(14_400),
+            submitted: 1_000,
+            submission_deposit: Deposit {
+                who: who.clone(),
+                amount: 10,
+            },
+            decision_deposit: Some(Deposit { who, amount: 200 }),
+            deciding: Some(DecidingStatus {
+                since: 2_000,
+                confirming: None,
+            }),
+            tally: Tally {
+                ayes: 300,
+                nays: 100,
+                support: 50,
+            },
+            in_queue: false,
+            alarm: Some((3_000, (3_000, 0))),
+        };
+        let info = ReferendumInfo::Ongoing(status.clone());
+        let encoded = info.encode();
+        assert_eq!(&encoded[..5], &[0, 33, 0, 22, 11]);
+        let decoded = ReferendumInfo::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded.ongoing(), Some(&status));
+        assert_eq!(status.tally.approval(), Perbill::from_percent(75));
+
+        let root = ReferendumOrigin::decode(&mut [0, 0].as_slice()).unwrap();
+        assert_eq!(root, ReferendumOrigin::Root);
+
+        // Approved at block 5000 without deposits
+        let decoded = ReferendumInfo::decode(&mut [1, 136, 19, 0, 0, 0, 0].as_slice()).unwrap();
+        assert_eq!(decoded, ReferendumInfo::Approved(5_000, None, None));
+        assert_eq!(decoded.ended_at(), Some(5_000));
+    }
+}
@@ -1,10 +1,16 @@
 use parity_scale_codec::{Compact, Decode, Encode};
+use sp_core::crypto::AccountId32;
 
 use crate::client::{Api, Result, Signer};
-use crate::network::SubstrateNetwork;
+use crate::network::{HasStaking, HasSubstrateStaking};
+use crate::pallets::storage::{
+    storage_staking_bonded, storage_staking_claimed_rewards, storage_staking_eras_stakers_overview,
+    storage_staking_ledger, storage_staking_nominators, storage_staking_payee,
+    storage_staking_slashing_spans, storage_staking_validators, storage_value_key,
+};
 use crate::pallets::CallIndex;
-use crate::rpc::RpcClient;
-use crate::{Balance, GenericAddress, UncheckedExtrinsic};
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, GenericAddress, Perbill, UncheckedExtrinsic, H256};
 
 pub type ComposedStakingBond = (
     CallIndex,
@@ -17,6 +23,14 @@ pub type ComposedStakingWithdrawUnbonded = (CallIndex, u32);
 pub type ComposedStakingNominate = (CallIndex, Vec<GenericAddress>);
 pub type ComposedStakingChill = CallIndex;
 pub type ComposedStakingRebond = (CallIndex, Compact<Balance>);
+pub type ComposedStakingValidate = (CallIndex, ValidatorPrefs);
+pub type ComposedStakingSetPayee = (CallIndex, RewardDestination<AccountId32>);
+pub type ComposedStakingSetController = CallIndex;
+pub type ComposedStakingPayoutStakers = (CallIndex, AccountId32, EraIndex);
+pub type ComposedStakingPayoutStakersByPage = (CallIndex, AccountId32, EraIndex, u32);
+pub type ComposedStakingKick = (CallIndex, Vec<GenericAddress>);
+pub type ComposedStakingReapStash = (CallIndex, AccountId32, u32);
+pub type ComposedStakingChillOther = (CallIndex, AccountId32);
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
 pub enum RewardDestination<Account> {
@@ -27,7 +41,144 @@ pub enum RewardDestination<Account> {
     None,
 }
 
-impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
+/// Counter for the number of eras that have passed.
+pub type EraIndex = u32;
+
+/// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be
+/// unlocked.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/staking/src/lib.rs
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct UnlockChunk {
+    /// Amount of funds to be unlocked.
+    #[codec(compact)]
+    pub value: Balance,
+    /// Era number at which point it'll be unlocked.
+    #[codec(compact)]
+    pub era: EraIndex,
+}
+
+/// The ledger of a (bonded) stash.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct StakingLedger {
+    /// The stash account whose balance is actually locked and at stake.
+    pub stash: AccountId32,
+    /// The total amount of the stash's balance that we are currently
+    /// accounting for. It's just `active` plus all the `unlocking`
+    /// balances.
+    #[codec(compact)]
+    pub total: Balance,
+    /// The total amount of the stash's balance that will be at stake in any
+    /// forthcoming rounds.
+    #[codec(compact)]
+    pub active: Balance,
+    /// Any balance that is becoming free, which may eventually be transferred
+    /// out of the stash (assuming it doesn't get slashed first).
+    pub unlocking: Vec<UnlockChunk>,
+    /// List of eras for which the stakers behind a validator have claimed
+    /// rewards. Only updated for validators.
+    pub legacy_claimed_rewards: Vec<EraIndex>,
+}
+
+impl StakingLedger {
+    /// Total amount that is unbonding, whether it can be withdrawn yet or not
+    pub fn unbonding(&self) -> Balance {
+        self.unlocking.iter().map(|c| c.value).sum()
+    }
+
+    /// Amount that `withdraw_unbonded` would withdraw now. The pallet
+    /// consolidates the chunks against `CurrentEra`, not `ActiveEra`, so pass
+    /// [`Api::staking_current_era`].
+    pub fn withdrawable(&self, current_era: EraIndex) -> Balance {
+        self.unlocking
+            .iter()
+            .filter(|c| c.era <= current_era)
+            .map(|c| c.value)
+            .sum()
+    }
+}
+
+/// A record of the nominations made by a specific account.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Nominations {
+    /// The targets of nomination.
+    pub targets: Vec<AccountId32>,
+    /// The era the nominations were submitted.
+    ///
+    /// Except for initial nominations which are considered submitted at era
+    /// 0.
+    pub submitted_in: EraIndex,
+    /// Whether the nominations have been suppressed. This can happen due to
+    /// slashing of the validators, or other events that might invalidate
+    /// the nomination.
+    pub suppressed: bool,
+}
+
+/// Preference of what happens regarding validation.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct ValidatorPrefs {
+    /// Reward that validator takes up-front; only the rest is split between
+    /// themselves and nominators.
+    #[codec(compact)]
+    pub commission: Perbill,
+    /// Whether or not this validator is accepting more nominations. If
+    /// `true`, then no nominator who is not already nominating this
+    /// validator may nominate them.
+    pub blocked: bool,
+}
+
+/// Information regarding the active era (era in used in session).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct ActiveEraInfo {
+    /// Index of era.
+    pub index: EraIndex,
+    /// Moment of start expressed as millisecond from `$UNIX_EPOCH`.
+    ///
+    /// Start can be none if start hasn't been set for the era yet,
+    /// Start is set on the first on_finalize of the era to guarantee usage of
+    /// `Time`.
+    pub start: Option<u64>,
+}
+
+/// Metadata for the paged exposure of a validator in an era. The nominator
+/// exposures themselves are stored in `ErasStakersPaged` pages.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Encode, Decode)]
+pub struct PagedExposureMetadata {
+    /// The total balance backing this validator.
+    #[codec(compact)]
+    pub total: Balance,
+    /// The validator's own stash that is exposed.
+    #[codec(compact)]
+    pub own: Balance,
+    /// Number of nominators backing this validator.
+    pub nominator_count: u32,
+    /// Number of pages of nominators.
+    pub page_count: u32,
+}
+
+/// Slashing spans for a stash account.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct SlashingSpans {
+    /// The index of the current slashing span of the nominator. Starts at 0
+    /// and counts up.
+    pub span_index: u32,
+    /// The start era of the most recent (ongoing) slashing span.
+    pub last_start: EraIndex,
+    /// The last era at which a non-zero slash occurred.
+    pub last_nonzero_slash: EraIndex,
+    /// All prior slashing spans' start indices, in reverse order (most recent
+    /// first) encoded as offsets relative to the slashing span after it.
+    pub prior: Vec<EraIndex>,
+}
+
+impl SlashingSpans {
+    /// Number of slashing spans, which is the value expected as
+    /// `num_slashing_spans` by `withdraw_unbonded` and `reap_stash`
+    pub fn num_spans(&self) -> u32 {
+        self.prior.len() as u32 + 1
+    }
+}
+
+impl<S: Signer, Client: RpcClient, N: HasStaking> Api<'_, S, Client, N> {
     pub fn staking_bond(
         &self,
         amount: Balance,
@@ -35,7 +186,7 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedStakingBond>> {
         let call = (
-            [N::STAKING_PALLET_IDX, N::STAKING_BOND],
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_BOND])?,
             Compact(amount),
             payee,
         );
@@ -48,7 +199,7 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedStakingBondExtra>> {
         let call = (
-            [N::STAKING_PALLET_IDX, N::STAKING_BOND_EXTRA],
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_BOND_EXTRA])?,
             Compact(amount),
         );
         self._create_xt(call, nonce)
@@ -59,7 +210,10 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         amount: Balance,
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedStakingUnbond>> {
-        let call = ([N::STAKING_PALLET_IDX, N::STAKING_UNBOND], Compact(amount));
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_UNBOND])?,
+            Compact(amount),
+        );
         self._create_xt(call, nonce)
     }
 
@@ -69,18 +223,36 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedStakingWithdrawUnbonded>> {
         let call = (
-            [N::STAKING_PALLET_IDX, N::STAKING_WITHDRAW_UNBONDED],
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_WITHDRAW_UNBONDED])?,
             num_slashing_spans,
         );
         self._create_xt(call, nonce)
     }
 
+    /// Same as `staking_withdraw_unbonded`, but looks up
+    /// `num_slashing_spans` of the stash the signer controls
+    pub fn staking_withdraw_unbonded_auto(
+        &self,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingWithdrawUnbonded>> {
+        let controller = self.signer_account()?;
+        let stash = match self.staking_ledger(controller.clone(), None)? {
+            Some(ledger) => ledger.stash,
+            None => controller,
+        };
+        let num_slashing_spans = self.staking_num_slashing_spans(stash, None)?;
+        self.staking_withdraw_unbonded(num_slashing_spans, nonce)
+    }
+
     pub fn staking_nominate(
         &self,
         targets: Vec<GenericAddress>,
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedStakingNominate>> {
-        let call = ([N::STAKING_PALLET_IDX, N::STAKING_NOMINATE], targets);
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_NOMINATE])?,
+            targets,
+        );
         self._create_xt(call, nonce)
     }
 
@@ -88,7 +260,10 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         &self,
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedStakingChill>> {
-        self._create_xt([N::STAKING_PALLET_IDX, N::STAKING_CHILL], nonce)
+        self._create_xt(
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_CHILL])?,
+            nonce,
+        )
     }
 
     pub fn staking_rebond(
@@ -96,7 +271,345 @@ impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
         amount: Balance,
         nonce: Option<u32>,
     ) -> Result<UncheckedExtrinsic<ComposedStakingRebond>> {
-        let call = ([N::STAKING_PALLET_IDX, N::STAKING_REBOND], Compact(amount));
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_REBOND])?,
+            Compact(amount),
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S: Signer, Client: RpcClient, N: HasSubstrateStaking> Api<'_, S, Client, N> {
+    /// Declare the desire to validate for the signer's stash
+    pub fn staking_validate(
+        &self,
+        prefs: ValidatorPrefs,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingValidate>> {
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_VALIDATE])?,
+            prefs,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Set the destination of the signer's staking rewards
+    pub fn staking_set_payee(
+        &self,
+        payee: RewardDestination<AccountId32>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingSetPayee>> {
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_SET_PAYEE])?,
+            payee,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Set the controller of the signer's stash to the stash itself
+    pub fn staking_set_controller(
+        &self,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingSetController>> {
+        self._create_xt(
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_SET_CONTROLLER])?,
+            nonce,
+        )
+    }
+
+    /// Pay out the next page of the stakers behind a validator for the given
+    /// era. Can be signed by anyone.
+    pub fn staking_payout_stakers(
+        &self,
+        validator_stash: AccountId32,
+        era: EraIndex,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingPayoutStakers>> {
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_PAYOUT_STAKERS])?,
+            validator_stash,
+            era,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Pay out a specific page of the stakers behind a validator for the given
+    /// era. Can be signed by anyone.
+    pub fn staking_payout_stakers_by_page(
+        &self,
+        validator_stash: AccountId32,
+        era: EraIndex,
+        page: u32,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingPayoutStakersByPage>> {
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_PAYOUT_STAKERS_BY_PAGE])?,
+            validator_stash,
+            era,
+            page,
+        );
         self._create_xt(call, nonce)
     }
+
+    /// Remove the given nominations from the signing validator
+    pub fn staking_kick(
+        &self,
+        who: Vec<GenericAddress>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingKick>> {
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_KICK])?,
+            who,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Remove all data structures concerning a staker/stash once it is at a
+    /// state where it can be considered `dust` in the staking system
+    pub fn staking_reap_stash(
+        &self,
+        stash: AccountId32,
+        num_slashing_spans: u32,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingReapStash>> {
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_REAP_STASH])?,
+            stash,
+            num_slashing_spans,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Declare a `stash` to no longer participate as a validator or nominator,
+    /// when the chill threshold is met
+    pub fn staking_chill_other(
+        &self,
+        stash: AccountId32,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedStakingChillOther>> {
+        let call = (
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_CHILL_OTHER])?,
+            stash,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasStaking> Api<'_, S, Client, N> {
+    /// Returns the staking ledger of a controller account
+    pub fn staking_ledger<A: Into<AccountId32>>(
+        &self,
+        controller: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<StakingLedger>> {
+        let storage_key = storage_staking_ledger(controller.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the staking ledger of a stash account by looking up its
+    /// controller first
+    pub fn staking_stash_ledger<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<StakingLedger>> {
+        match self.staking_bonded(stash, at_block)? {
+            Some(controller) => self.staking_ledger(controller, at_block),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the controller account of a bonded stash
+    pub fn staking_bonded<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<AccountId32>> {
+        let storage_key = storage_staking_bonded(stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns where the rewards of a stash are paid
+    pub fn staking_payee<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<RewardDestination<AccountId32>>> {
+        let storage_key = storage_staking_payee(stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the nominations of a nominating stash
+    pub fn staking_nominators<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<Nominations>> {
+        let storage_key = storage_staking_nominators(stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the preferences of a validating stash
+    pub fn staking_validators<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<ValidatorPrefs>> {
+        let storage_key = storage_staking_validators(stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the slashing spans of a stash
+    pub fn staking_slashing_spans<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<SlashingSpans>> {
+        let storage_key = storage_staking_slashing_spans(stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the number of slashing spans of a stash, 0 if it was never
+    /// slashed
+    pub fn staking_num_slashing_spans<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<u32> {
+        Ok(self
+            .staking_slashing_spans(stash, at_block)?
+            .map(|s| s.num_spans())
+            .unwrap_or_default())
+    }
+
+    /// Returns the active era information
+    pub fn staking_active_era(&self, at_block: Option<H256>) -> Result<Option<ActiveEraInfo>> {
+        let storage_key = storage_value_key("Staking", "ActiveEra");
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the current era index, the latest planned era
+    pub fn staking_current_era(&self, at_block: Option<H256>) -> Result<Option<EraIndex>> {
+        let storage_key = storage_value_key("Staking", "CurrentEra");
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the minimum active bond to become and maintain the role of a
+    /// nominator
+    pub fn staking_min_nominator_bond(&self, at_block: Option<H256>) -> Result<Option<Balance>> {
+        let storage_key = storage_value_key("Staking", "MinNominatorBond");
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+}
+
+impl<S, Client: RpcClient, N: HasSubstrateStaking> Api<'_, S, Client, N> {
+    /// Returns the exposure metadata of a validator elected in `era`
+    pub fn staking_eras_stakers_overview<A: Into<AccountId32>>(
+        &self,
+        era: EraIndex,
+        validator_stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<PagedExposureMetadata>> {
+        let storage_key =
+            storage_staking_eras_stakers_overview(era, validator_stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the exposure pages of a validator for which rewards were
+    /// claimed in `era`
+    pub fn staking_claimed_rewards<A: Into<AccountId32>>(
+        &self,
+        era: EraIndex,
+        validator_stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Vec<u32>> {
+        let storage_key = storage_staking_claimed_rewards(era, validator_stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staking_ledger_decode() {
+        let encoded = hex::decode(concat!(
+            // stash
+            "ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f",
+            // total: compact(3_000_000_000_000)
+            "0b0030ef7dba02",
+            // active: compact(1_000_000_000_000)
+            "070010a5d4e8",
+            // unlocking: 2 chunks, (1_000_000_000_000, 1400), (1_000_000_000_000, 1402)
+            "08",
+            "070010a5d4e8",
+            "e115",
+            "070010a5d4e8",
+            "e915",
+            // legacy_claimed_rewards: [1390]
+            "04",
+            "6e050000",
+        ))
+        .unwrap();
+        let ledger = StakingLedger::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(ledger.total, 3_000_000_000_000);
+        assert_eq!(ledger.active, 1_000_000_000_000);
+        assert_eq!(
+            ledger.unlocking,
+            vec![
+                UnlockChunk {
+                    value: 1_000_000_000_000,
+                    era: 1400
+                },
+                UnlockChunk {
+                    value: 1_000_000_000_000,
+                    era: 1402
+                }
+            ]
+        );
+        assert_eq!(ledger.legacy_claimed_rewards, vec![1390]);
+        assert_eq!(ledger.unbonding(), 2_000_000_000_000);
+        assert_eq!(ledger.withdrawable(1399), 0);
+        assert_eq!(ledger.withdrawable(1400), 1_000_000_000_000);
+        assert_eq!(ledger.withdrawable(1401), 1_000_000_000_000);
+    }
+
+    #[test]
+    fn slashing_spans_decode() {
+        // span_index: 2, last_start: 1200, last_nonzero_slash: 1200, prior: [10, 5]
+        let encoded = hex::decode("02000000b0040000b0040000080a00000005000000").unwrap();
+        let spans = SlashingSpans::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(spans.prior, vec![10, 5]);
+        assert_eq!(spans.num_spans(), 3);
+    }
+
+    #[test]
+    fn validator_prefs_decode() {
+        // compact(50_000_000) = 5%, not blocked
+        let prefs =
+            ValidatorPrefs::decode(&mut hex::decode("02c2eb0b00").unwrap().as_slice()).unwrap();
+        assert_eq!(
+            prefs,
+            ValidatorPrefs {
+                commission: Perbill::from_percent(5),
+                blocked: false,
+            }
+        );
+    }
 }
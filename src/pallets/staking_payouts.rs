@@ -0,0 +1,144 @@
+use std::ops::Range;
+
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasSubstrateStaking;
+use crate::pallets::staking::{ComposedStakingPayoutStakersByPage, EraIndex};
+use crate::pallets::utility::ComposedUtilityBatch;
+use crate::rpc::RpcClient;
+use crate::{UncheckedExtrinsic, H256};
+
+/// Exposure pages of a validator in an era whose rewards were not claimed yet
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnclaimedReward {
+    pub era: EraIndex,
+    pub pages: Vec<u32>,
+}
+
+/// Eras that can be paid out, i.e. eras that ended and did not fall out of
+/// the history yet
+pub fn claimable_eras(
+    current_era: EraIndex,
+    active_era: EraIndex,
+    history_depth: u32,
+) -> Range<EraIndex> {
+    current_era.saturating_sub(history_depth)..active_era
+}
+
+/// Pages out of `page_count` that are not in `claimed`. A validator without
+/// nominators still has a single page to claim its own reward.
+pub fn unclaimed_pages(page_count: u32, claimed: &[u32]) -> Vec<u32> {
+    (0..page_count.max(1))
+        .filter(|page| !claimed.contains(page))
+        .collect()
+}
+
+impl<S, Client: RpcClient, N: HasSubstrateStaking> Api<'_, S, Client, N> {
+    /// Returns the eras, oldest first, in which `validator_stash` was elected
+    /// and has exposure pages that were not paid out yet.
+    ///
+    /// Eras claimed before paged exposures were introduced are read from the
+    /// ledger's `legacy_claimed_rewards`.
+    pub fn staking_unclaimed_rewards<A: Into<AccountId32>>(
+        &self,
+        validator_stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Vec<UnclaimedReward>> {
+        let stash = validator_stash.into();
+        let current_era = self.staking_current_era(at_block)?.unwrap_or_default();
+        let active_era = self
+            .staking_active_era(at_block)?
+            .map(|e| e.index)
+            .unwrap_or_default();
+        let legacy_claimed = self
+            .staking_stash_ledger(stash.clone(), at_block)?
+            .map(|l| l.legacy_claimed_rewards)
+            .unwrap_or_default();
+
+        let mut unclaimed = Vec::new();
+        for era in claimable_eras(current_era, active_era, N::STAKING_HISTORY_DEPTH) {
+            if legacy_claimed.contains(&era) {
+                continue;
+            }
+            let Some(overview) =
+                self.staking_eras_stakers_overview(era, stash.clone(), at_block)?
+            else {
+                // not elected in this era
+                continue;
+            };
+            let claimed = self.staking_claimed_rewards(era, stash.clone(), at_block)?;
+            let pages = unclaimed_pages(overview.page_count, &claimed);
+            if !pages.is_empty() {
+                unclaimed.push(UnclaimedReward { era, pages });
+            }
+        }
+        Ok(unclaimed)
+    }
+}
+
+impl<S: Signer, Client: RpcClient, N: HasSubstrateStaking> Api<'_, S, Client, N> {
+    /// Creates `payout_stakers_by_page` calls for every unclaimed page of
+    /// `validator_stash`, oldest era first, and wraps them in
+    /// `utility.force_batch` extrinsics of at most `batch_size` calls each.
+    ///
+    /// The extrinsics use consecutive nonces starting at `nonce`, or at the
+    /// signer's current nonce if `None`.
+    pub fn staking_payout_unclaimed<A: Into<AccountId32>>(
+        &self,
+        validator_stash: A,
+        batch_size: usize,
+        nonce: Option<u32>,
+    ) -> Result<Vec<UncheckedExtrinsic<ComposedUtilityBatch<ComposedStakingPayoutStakersByPage>>>>
+    {
+        let stash = validator_stash.into();
+        let call_index =
+            self.call_index([N::STAKING_PALLET_IDX, N::STAKING_PAYOUT_STAKERS_BY_PAGE])?;
+        let calls: Vec<ComposedStakingPayoutStakersByPage> = self
+            .staking_unclaimed_rewards(stash.clone(), None)?
+            .into_iter()
+            .flat_map(|r| {
+                let stash = stash.clone();
+                r.pages
+                    .into_iter()
+                    .map(move |page| (call_index, stash.clone(), r.era, page))
+            })
+            .collect();
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => self.nonce()?,
+        };
+        calls
+            .chunks(batch_size.max(1))
+            .zip(nonce..)
+            .map(|(batch, nonce)| self.utility_force_batch(batch.to_vec(), Some(nonce)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claimable_eras_range() {
+        assert_eq!(claimable_eras(1500, 1500, 84), 1416..1500);
+        // next era is already planned
+        assert_eq!(claimable_eras(1501, 1500, 84), 1417..1500);
+        assert_eq!(claimable_eras(10, 10, 84), 0..10);
+    }
+
+    #[test]
+    fn unclaimed_pages_filter() {
+        assert_eq!(unclaimed_pages(3, &[]), vec![0, 1, 2]);
+        assert_eq!(unclaimed_pages(3, &[0, 2]), vec![1]);
+        assert!(unclaimed_pages(2, &[1, 0]).is_empty());
+        // validator without nominators
+        assert_eq!(unclaimed_pages(0, &[]), vec![0]);
+        assert!(unclaimed_pages(0, &[0]).is_empty());
+    }
+}
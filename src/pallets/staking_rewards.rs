@@ -0,0 +1,391 @@
+use std::ops::Range;
+
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::AccountId32;
+use sp_core::storage::StorageKey;
+
+use crate::client::{Api, Result};
+use crate::network::HasSubstrateStaking;
+use crate::pallets::staking::{EraIndex, PagedExposureMetadata, ValidatorPrefs};
+use crate::pallets::storage::{
+    storage_staking_eras_reward_points, storage_staking_eras_stakers_paged,
+    storage_staking_eras_stakers_paged_prefix, storage_staking_eras_validator_prefs,
+    storage_staking_eras_validator_reward,
+};
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, Perbill, H256};
+
+/// Milliseconds in a (julian) year
+const YEAR_MS: u64 = 36525 * 24 * 60 * 60 * 10;
+
+/// Reward points of an era. Used to split era total payout between
+/// validators.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Encode, Decode)]
+pub struct EraRewardPoints {
+    /// Total number of points. Equals the sum of reward points for each
+    /// validator.
+    pub total: u32,
+    /// The reward points earned by a given validator.
+    pub individual: Vec<(AccountId32, u32)>,
+}
+
+impl EraRewardPoints {
+    /// Points earned by `validator`, 0 if it earned none
+    pub fn points_of(&self, validator: &AccountId32) -> u32 {
+        self.individual
+            .iter()
+            .find(|(v, _)| v == validator)
+            .map(|(_, p)| *p)
+            .unwrap_or_default()
+    }
+}
+
+/// The amount of exposure (to slashing) that an individual nominator has.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct IndividualExposure {
+    /// The stash account of the nominator in question.
+    pub who: AccountId32,
+    /// Amount of funds exposed.
+    #[codec(compact)]
+    pub value: Balance,
+}
+
+/// A snapshot of the stake backing a single validator in the system.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Encode, Decode)]
+pub struct ExposurePage {
+    /// The total balance of this chunk/page.
+    #[codec(compact)]
+    pub page_total: Balance,
+    /// The portions of nominators stashes that are exposed.
+    pub others: Vec<IndividualExposure>,
+}
+
+/// The reward of a stash in an era
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct EraReward {
+    pub era: EraIndex,
+    /// Stake of the stash that was exposed in the era
+    pub stake: Balance,
+    pub reward: Balance,
+}
+
+/// Splits the part of `era_payout` earned by a validator with `points` out of
+/// `total_points` into its commission and the leftover shared by stake.
+pub fn validator_reward_parts(
+    era_payout: Balance,
+    points: u32,
+    total_points: u32,
+    commission: Perbill,
+) -> (Balance, Balance) {
+    let validator_payout = Perbill::from_rational(points.into(), total_points.into()) * era_payout;
+    let commission_payout = commission * validator_payout;
+    (
+        commission_payout,
+        validator_payout.saturating_sub(commission_payout),
+    )
+}
+
+/// Part of the `leftover` of a validator paid to `stake` out of the
+/// validator's `total` exposure.
+pub fn stake_reward(leftover: Balance, stake: Balance, total: Balance) -> Balance {
+    Perbill::from_rational(stake, total) * leftover
+}
+
+/// The average reward per staked unit and era, extrapolated to a year of eras
+/// lasting `era_duration_ms`. Rewards are not compounded.
+pub fn annualized_yield(rewards: &[EraReward], era_duration_ms: u64) -> f64 {
+    let per_era: Vec<f64> = rewards
+        .iter()
+        .filter(|r| r.stake > 0)
+        .map(|r| r.reward as f64 / r.stake as f64)
+        .collect();
+    if per_era.is_empty() || era_duration_ms == 0 {
+        return 0.0;
+    }
+    let eras_per_year = YEAR_MS as f64 / era_duration_ms as f64;
+    per_era.iter().sum::<f64>() / per_era.len() as f64 * eras_per_year
+}
+
+/// What a validator earned in an era, before it is split between stakers
+struct ValidatorEraPayout {
+    commission: Balance,
+    leftover: Balance,
+    exposure: PagedExposureMetadata,
+}
+
+impl ValidatorEraPayout {
+    /// Adds the reward of `stake` exposed to the validator to `reward`
+    fn add_stake_reward(&self, reward: &mut Option<EraReward>, era: EraIndex, stake: Balance) {
+        let r = reward.get_or_insert(EraReward {
+            era,
+            stake: 0,
+            reward: 0,
+        });
+        r.stake += stake;
+        r.reward += stake_reward(self.leftover, stake, self.exposure.total);
+    }
+}
+
+/// The validator of an `ErasStakersPaged` key, the raw tail of its
+/// `Twox64Concat` hash followed by the hashed page
+fn exposure_page_validator(key: &StorageKey) -> AccountId32 {
+    let end = key.0.len() - 12;
+    let validator: [u8; 32] = key.0[end - 32..end].try_into().expect("32 byte slice");
+    AccountId32::new(validator)
+}
+
+impl<S, Client: RpcClient, N: HasSubstrateStaking> Api<'_, S, Client, N> {
+    /// Returns the total payout of `era`, only set once the era ended
+    pub fn staking_eras_validator_reward(
+        &self,
+        era: EraIndex,
+        at_block: Option<H256>,
+    ) -> Result<Option<Balance>> {
+        let storage_key = storage_staking_eras_validator_reward(era);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the reward points of the validators in `era`
+    pub fn staking_eras_reward_points(
+        &self,
+        era: EraIndex,
+        at_block: Option<H256>,
+    ) -> Result<Option<EraRewardPoints>> {
+        let storage_key = storage_staking_eras_reward_points(era);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the preferences of a validator in `era`
+    pub fn staking_eras_validator_prefs<A: Into<AccountId32>>(
+        &self,
+        era: EraIndex,
+        validator_stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<ValidatorPrefs>> {
+        let storage_key =
+            storage_staking_eras_validator_prefs(era, validator_stash.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns a page of the nominators exposed to a validator in `era`
+    pub fn staking_eras_stakers_paged<A: Into<AccountId32>>(
+        &self,
+        era: EraIndex,
+        validator_stash: A,
+        page: u32,
+        at_block: Option<H256>,
+    ) -> Result<Option<ExposurePage>> {
+        let storage_key =
+            storage_staking_eras_stakers_paged(era, validator_stash.into().as_ref(), page);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    fn validator_era_payout(
+        &self,
+        validator_stash: &AccountId32,
+        era: EraIndex,
+        at_block: Option<H256>,
+    ) -> Result<Option<ValidatorEraPayout>> {
+        let Some(exposure) =
+            self.staking_eras_stakers_overview(era, validator_stash.clone(), at_block)?
+        else {
+            return Ok(None);
+        };
+        let Some(era_payout) = self.staking_eras_validator_reward(era, at_block)? else {
+            return Ok(None);
+        };
+        let points = self
+            .staking_eras_reward_points(era, at_block)?
+            .unwrap_or_default();
+        let commission = self
+            .staking_eras_validator_prefs(era, validator_stash.clone(), at_block)?
+            .unwrap_or_default()
+            .commission;
+        let (commission, leftover) = validator_reward_parts(
+            era_payout,
+            points.points_of(validator_stash),
+            points.total,
+            commission,
+        );
+        Ok(Some(ValidatorEraPayout {
+            commission,
+            leftover,
+            exposure,
+        }))
+    }
+
+    /// Returns what `validator_stash` earned in `era` from its commission and
+    /// own stake, `None` if it was not elected or the era did not end yet
+    pub fn staking_validator_era_reward<A: Into<AccountId32>>(
+        &self,
+        validator_stash: A,
+        era: EraIndex,
+        at_block: Option<H256>,
+    ) -> Result<Option<EraReward>> {
+        let stash = validator_stash.into();
+        Ok(self
+            .validator_era_payout(&stash, era, at_block)?
+            .map(|p| EraReward {
+                era,
+                stake: p.exposure.own,
+                reward: p.commission + stake_reward(p.leftover, p.exposure.own, p.exposure.total),
+            }))
+    }
+
+    /// Returns what `nominator` earned in `era` from its exposure to any of
+    /// `validators`, `None` if it was not exposed to any of them
+    pub fn staking_nominator_era_reward<A: Into<AccountId32>>(
+        &self,
+        nominator: A,
+        era: EraIndex,
+        validators: &[AccountId32],
+        at_block: Option<H256>,
+    ) -> Result<Option<EraReward>> {
+        let nominator = nominator.into();
+        let mut reward: Option<EraReward> = None;
+        for validator in validators {
+            let Some(payout) = self.validator_era_payout(validator, era, at_block)? else {
+                continue;
+            };
+            for page in 0..payout.exposure.page_count {
+                let exposure = self
+                    .staking_eras_stakers_paged(era, validator.clone(), page, at_block)?
+                    .unwrap_or_default();
+                if let Some(e) = exposure.others.iter().find(|e| e.who == nominator) {
+                    payout.add_stake_reward(&mut reward, era, e.value);
+                    break;
+                }
+            }
+        }
+        Ok(reward)
+    }
+
+    /// Returns the validators `nominator` was exposed to in `era`, with its
+    /// stake behind each. Every exposure page of the era is read, which is a
+    /// few hundred storage values on the relay chains.
+    pub fn staking_nominator_era_exposures<A: Into<AccountId32>>(
+        &self,
+        nominator: A,
+        era: EraIndex,
+        at_block: Option<H256>,
+    ) -> Result<Vec<(AccountId32, Balance)>> {
+        let nominator = nominator.into();
+        let keys = self.storage_keys(storage_staking_eras_stakers_paged_prefix(era), at_block)?;
+        Ok(self
+            .storage_values::<ExposurePage>(&keys, at_block)?
+            .into_iter()
+            .filter_map(|(key, page)| {
+                let e = page.others.into_iter().find(|e| e.who == nominator)?;
+                Some((exposure_page_validator(&key), e.value))
+            })
+            .collect())
+    }
+
+    /// Returns the rewards of `stash` for each of `eras` it earned anything in.
+    ///
+    /// Validators are recognized by their exposure. Nominators are looked up
+    /// in the exposures of each era, see
+    /// [`Api::staking_nominator_era_exposures`], so eras before a change of
+    /// targets count the validators nominated then. Era information is only
+    /// kept for `STAKING_HISTORY_DEPTH` eras, so `at_block` must be recent
+    /// enough for `eras`.
+    pub fn staking_rewards_history<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        eras: Range<EraIndex>,
+        at_block: Option<H256>,
+    ) -> Result<Vec<EraReward>> {
+        let stash = stash.into();
+        let mut rewards = Vec::new();
+        for era in eras {
+            let mut reward = self.staking_validator_era_reward(stash.clone(), era, at_block)?;
+            if reward.is_none() {
+                for (validator, stake) in
+                    self.staking_nominator_era_exposures(stash.clone(), era, at_block)?
+                {
+                    if let Some(payout) = self.validator_era_payout(&validator, era, at_block)? {
+                        payout.add_stake_reward(&mut reward, era, stake);
+                    }
+                }
+            }
+            rewards.extend(reward);
+        }
+        Ok(rewards)
+    }
+
+    /// Returns the annualized yield of `stash` over `eras`, see
+    /// [`annualized_yield`]
+    pub fn staking_annualized_yield<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        eras: Range<EraIndex>,
+        at_block: Option<H256>,
+    ) -> Result<f64> {
+        let rewards = self.staking_rewards_history(stash, eras, at_block)?;
+        Ok(annualized_yield(&rewards, N::STAKING_ERA_DURATION_MS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perbill_math() {
+        assert_eq!(Perbill::from_rational(1, 3), Perbill(333_333_333));
+        assert_eq!(Perbill::from_rational(2, 3), Perbill(666_666_666));
+        assert_eq!(Perbill::from_rational(5, 3), Perbill(1_000_000_000));
+        assert_eq!(Perbill::from_percent(10) * 1_000u128, 100);
+        // rounds to nearest, preferring down
+        assert_eq!(Perbill(500_000_000) * 3u128, 1);
+        assert_eq!(Perbill(500_000_001) * 3u128, 2);
+    }
+
+    #[test]
+    fn validator_reward_split() {
+        // 10% of points, 5% commission
+        let (commission, leftover) =
+            validator_reward_parts(1_000_000, 100, 1_000, Perbill::from_percent(5));
+        assert_eq!(commission, 5_000);
+        assert_eq!(leftover, 95_000);
+        // a quarter of the exposure
+        assert_eq!(stake_reward(leftover, 250, 1_000), 23_750);
+    }
+
+    #[test]
+    fn exposure_page_key_validator() {
+        let validator = AccountId32::new([7; 32]);
+        let key = storage_staking_eras_stakers_paged(1_000, validator.as_ref(), 2);
+        let prefix = storage_staking_eras_stakers_paged_prefix(1_000);
+        assert!(key.0.starts_with(&prefix.0));
+        assert_eq!(exposure_page_validator(&key), validator);
+    }
+
+    #[test]
+    fn yield_per_year() {
+        let rewards = [
+            EraReward {
+                era: 1,
+                stake: 1_000_000,
+                reward: 400,
+            },
+            EraReward {
+                era: 2,
+                stake: 1_000_000,
+                reward: 200,
+            },
+            EraReward {
+                era: 3,
+                stake: 0,
+                reward: 0,
+            },
+        ];
+        let apy = annualized_yield(&rewards, 24 * 60 * 60 * 1000);
+        assert!((apy - 0.0003 * 365.25).abs() < 1e-9);
+        assert_eq!(annualized_yield(&[], 24 * 60 * 60 * 1000), 0.0);
+    }
+}
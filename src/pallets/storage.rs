@@ -1,20 +1,299 @@
 use sp_core::storage::StorageKey;
 use sp_core::{blake2_128, twox_128, twox_64};
 
-pub(crate) fn storage_key_account_balance(account: &[u8]) -> StorageKey {
-    let pallet = twox_128("System".as_bytes());
-    let storage = twox_128("Account".as_bytes());
-    let key_hash: Vec<u8> = blake2_128(account).iter().chain(account).cloned().collect();
-    let key: Vec<u8> = pallet.into_iter().chain(storage).chain(key_hash).collect();
+/// `twox_128(pallet) ++ twox_128(storage)`, the key of a plain storage value
+/// and the prefix of every entry in a storage map.
+pub(crate) fn storage_value_key(pallet: &str, storage: &str) -> StorageKey {
+    let pallet = twox_128(pallet.as_bytes());
+    let storage = twox_128(storage.as_bytes());
+    StorageKey(pallet.into_iter().chain(storage).collect())
+}
+
+/// Appends hashed map keys to the prefix of `pallet`/`storage`.
+pub(crate) fn storage_map_key(pallet: &str, storage: &str, key_hashes: &[&[u8]]) -> StorageKey {
+    let mut key = storage_value_key(pallet, storage).0;
+    for key_hash in key_hashes {
+        key.extend_from_slice(key_hash);
+    }
     StorageKey(key)
 }
 
+pub(crate) fn twox_64_concat(key: &[u8]) -> Vec<u8> {
+    twox_64(key).iter().chain(key).cloned().collect()
+}
+
+pub(crate) fn blake2_128_concat(key: &[u8]) -> Vec<u8> {
+    blake2_128(key).iter().chain(key).cloned().collect()
+}
+
+pub(crate) fn storage_key_account_balance(account: &[u8]) -> StorageKey {
+    storage_map_key("System", "Account", &[&blake2_128_concat(account)])
+}
+
+pub(crate) fn storage_balances_locks(account: &[u8]) -> StorageKey {
+    storage_map_key("Balances", "Locks", &[&blake2_128_concat(account)])
+}
+
+pub(crate) fn storage_balances_freezes(account: &[u8]) -> StorageKey {
+    storage_map_key("Balances", "Freezes", &[&blake2_128_concat(account)])
+}
+
+pub(crate) fn storage_balances_holds(account: &[u8]) -> StorageKey {
+    storage_map_key("Balances", "Holds", &[&blake2_128_concat(account)])
+}
+
 pub(crate) fn storage_proxy_proxies(account: &[u8]) -> StorageKey {
-    let pallet = twox_128("Proxy".as_bytes());
-    let storage = twox_128("Proxies".as_bytes());
-    let key_hash: Vec<u8> = twox_64(account).iter().chain(account).cloned().collect();
-    let key: Vec<u8> = pallet.into_iter().chain(storage).chain(key_hash).collect();
-    StorageKey(key)
+    storage_map_key("Proxy", "Proxies", &[&twox_64_concat(account)])
+}
+
+pub(crate) fn storage_multisig_multisigs(account: &[u8], call_hash: &[u8]) -> StorageKey {
+    storage_map_key(
+        "Multisig",
+        "Multisigs",
+        &[&twox_64_concat(account), &blake2_128_concat(call_hash)],
+    )
+}
+
+pub(crate) fn storage_multisig_multisigs_prefix(account: &[u8]) -> StorageKey {
+    storage_map_key("Multisig", "Multisigs", &[&twox_64_concat(account)])
+}
+
+pub(crate) fn storage_staking_ledger(controller: &[u8]) -> StorageKey {
+    storage_map_key("Staking", "Ledger", &[&blake2_128_concat(controller)])
+}
+
+pub(crate) fn storage_staking_bonded(stash: &[u8]) -> StorageKey {
+    storage_map_key("Staking", "Bonded", &[&twox_64_concat(stash)])
+}
+
+pub(crate) fn storage_staking_payee(stash: &[u8]) -> StorageKey {
+    storage_map_key("Staking", "Payee", &[&twox_64_concat(stash)])
+}
+
+pub(crate) fn storage_staking_nominators(stash: &[u8]) -> StorageKey {
+    storage_map_key("Staking", "Nominators", &[&twox_64_concat(stash)])
+}
+
+pub(crate) fn storage_staking_validators(stash: &[u8]) -> StorageKey {
+    storage_map_key("Staking", "Validators", &[&twox_64_concat(stash)])
+}
+
+pub(crate) fn storage_staking_slashing_spans(stash: &[u8]) -> StorageKey {
+    storage_map_key("Staking", "SlashingSpans", &[&twox_64_concat(stash)])
+}
+
+pub(crate) fn storage_staking_claimed_rewards(era: u32, validator: &[u8]) -> StorageKey {
+    storage_map_key(
+        "Staking",
+        "ClaimedRewards",
+        &[
+            &twox_64_concat(&era.to_le_bytes()),
+            &twox_64_concat(validator),
+        ],
+    )
+}
+
+pub(crate) fn storage_staking_eras_stakers_overview(era: u32, validator: &[u8]) -> StorageKey {
+    storage_map_key(
+        "Staking",
+        "ErasStakersOverview",
+        &[
+            &twox_64_concat(&era.to_le_bytes()),
+            &twox_64_concat(validator),
+        ],
+    )
+}
+
+pub(crate) fn storage_nomination_pools_pool_members(member: &[u8]) -> StorageKey {
+    storage_map_key("NominationPools", "PoolMembers", &[&twox_64_concat(member)])
+}
+
+pub(crate) fn storage_nomination_pools_bonded_pools(pool_id: u32) -> StorageKey {
+    storage_map_key(
+        "NominationPools",
+        "BondedPools",
+        &[&twox_64_concat(&pool_id.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_nomination_pools_reward_pools(pool_id: u32) -> StorageKey {
+    storage_map_key(
+        "NominationPools",
+        "RewardPools",
+        &[&twox_64_concat(&pool_id.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_staking_eras_validator_reward(era: u32) -> StorageKey {
+    storage_map_key(
+        "Staking",
+        "ErasValidatorReward",
+        &[&twox_64_concat(&era.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_staking_eras_reward_points(era: u32) -> StorageKey {
+    storage_map_key(
+        "Staking",
+        "ErasRewardPoints",
+        &[&twox_64_concat(&era.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_staking_eras_validator_prefs(era: u32, validator: &[u8]) -> StorageKey {
+    storage_map_key(
+        "Staking",
+        "ErasValidatorPrefs",
+        &[
+            &twox_64_concat(&era.to_le_bytes()),
+            &twox_64_concat(validator),
+        ],
+    )
+}
+
+pub(crate) fn storage_staking_eras_stakers_paged(
+    era: u32,
+    validator: &[u8],
+    page: u32,
+) -> StorageKey {
+    storage_map_key(
+        "Staking",
+        "ErasStakersPaged",
+        &[
+            &twox_64_concat(&era.to_le_bytes()),
+            &twox_64_concat(validator),
+            &twox_64_concat(&page.to_le_bytes()),
+        ],
+    )
+}
+
+pub(crate) fn storage_staking_eras_stakers_paged_prefix(era: u32) -> StorageKey {
+    storage_map_key(
+        "Staking",
+        "ErasStakersPaged",
+        &[&twox_64_concat(&era.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_identity_identity_of(account: &[u8]) -> StorageKey {
+    storage_map_key("Identity", "IdentityOf", &[&twox_64_concat(account)])
+}
+
+pub(crate) fn storage_identity_super_of(account: &[u8]) -> StorageKey {
+    storage_map_key("Identity", "SuperOf", &[&blake2_128_concat(account)])
+}
+
+pub(crate) fn storage_identity_key_records(account: &[u8]) -> StorageKey {
+    storage_map_key("Identity", "KeyRecords", &[&twox_64_concat(account)])
+}
+
+pub(crate) fn storage_identity_authorizations_prefix(signatory: &[u8]) -> StorageKey {
+    storage_map_key(
+        "Identity",
+        "Authorizations",
+        &[&blake2_128_concat(signatory)],
+    )
+}
+
+pub(crate) fn storage_identity_authorizations_given_prefix(did: &[u8]) -> StorageKey {
+    storage_map_key(
+        "Identity",
+        "AuthorizationsGiven",
+        &[&blake2_128_concat(did)],
+    )
+}
+
+pub(crate) fn storage_asset_balance_of(asset_id: &[u8], did: &[u8]) -> StorageKey {
+    storage_map_key("Asset", "BalanceOf", &[&blake2_128_concat(asset_id), did])
+}
+
+pub(crate) fn storage_portfolio_portfolio_asset_balances(
+    portfolio: &[u8],
+    asset_id: &[u8],
+) -> StorageKey {
+    storage_map_key(
+        "Portfolio",
+        "PortfolioAssetBalances",
+        &[&twox_64_concat(portfolio), &blake2_128_concat(asset_id)],
+    )
+}
+
+pub(crate) fn storage_settlement_instruction_details(instruction_id: u64) -> StorageKey {
+    storage_map_key(
+        "Settlement",
+        "InstructionDetails",
+        &[&twox_64_concat(&instruction_id.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_voter_list_list_nodes(account: &[u8]) -> StorageKey {
+    storage_map_key("VoterList", "ListNodes", &[&twox_64_concat(account)])
+}
+
+pub(crate) fn storage_voter_list_list_bags(bag_upper: u64) -> StorageKey {
+    storage_map_key(
+        "VoterList",
+        "ListBags",
+        &[&twox_64_concat(&bag_upper.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_fast_unstake_queue(stash: &[u8]) -> StorageKey {
+    storage_map_key("FastUnstake", "Queue", &[&twox_64_concat(stash)])
+}
+
+pub(crate) fn storage_vesting_vesting(account: &[u8]) -> StorageKey {
+    storage_map_key("Vesting", "Vesting", &[&blake2_128_concat(account)])
+}
+
+pub(crate) fn storage_conviction_voting_voting_for(account: &[u8], class: u16) -> StorageKey {
+    storage_map_key(
+        "ConvictionVoting",
+        "VotingFor",
+        &[
+            &twox_64_concat(account),
+            &twox_64_concat(&class.to_le_bytes()),
+        ],
+    )
+}
+
+pub(crate) fn storage_conviction_voting_class_locks_for(account: &[u8]) -> StorageKey {
+    storage_map_key(
+        "ConvictionVoting",
+        "ClassLocksFor",
+        &[&twox_64_concat(account)],
+    )
+}
+
+pub(crate) fn storage_referenda_referendum_info_for(index: u32) -> StorageKey {
+    storage_map_key(
+        "Referenda",
+        "ReferendumInfoFor",
+        &[&blake2_128_concat(&index.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_indices_accounts(index: u32) -> StorageKey {
+    storage_map_key(
+        "Indices",
+        "Accounts",
+        &[&blake2_128_concat(&index.to_le_bytes())],
+    )
+}
+
+pub(crate) fn storage_assets_asset(id: u32) -> StorageKey {
+    storage_map_key("Assets", "Asset", &[&blake2_128_concat(&id.to_le_bytes())])
+}
+
+pub(crate) fn storage_assets_account(id: u32, account: &[u8]) -> StorageKey {
+    storage_map_key(
+        "Assets",
+        "Account",
+        &[
+            &blake2_128_concat(&id.to_le_bytes()),
+            &blake2_128_concat(account),
+        ],
+    )
 }
 
 #[cfg(test)]
@@ -46,4 +325,22 @@ mod tests {
         let expected = "1809d78346727a0ef58c0fa03bafa3231d885dcfb277f185f2d8e62a5f290c855e63108ebcfb0e35ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f";
         check(storage_proxy_proxies, expected);
     }
+
+    #[test]
+    fn multisig_multisigs_storage_key() {
+        let expected = "7474449cca95dc5d0c00e71735a6d17d3cd15a3fd6e04e47bee3922dbfa92c8d5e63108ebcfb0e35ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f";
+        check(storage_multisig_multisigs_prefix, expected);
+    }
+
+    #[test]
+    fn staking_storage_keys() {
+        let expected = "5f3e4907f716ac89b6347d15ececedca422adb579f1dbf4f3886c5cfa3bb8cc4f4aa2c6a213b1188832a85b0b63fc95bff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f";
+        check(storage_staking_ledger, expected);
+        let expected = "5f3e4907f716ac89b6347d15ececedca3ed14b45ed20d054f05e37e2542cfe705e63108ebcfb0e35ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f";
+        check(storage_staking_bonded, expected);
+        assert_eq!(
+            hex::encode(storage_value_key("Staking", "ActiveEra").0),
+            "5f3e4907f716ac89b6347d15ececedca487df464e44a534ba6b0cbb32407b587"
+        );
+    }
 }
@@ -0,0 +1,278 @@
+use parity_scale_codec::{Compact, Decode, Encode, Error, Input, Output};
+
+use crate::client::{Api, Result, Signer};
+use crate::network::{call_index, SubstrateNetwork};
+use crate::pallets::balances::ComposedTransfer;
+use crate::pallets::utility::ComposedUtilityBatch;
+use crate::pallets::CallIndex;
+use crate::rpc::RpcClient;
+use crate::{Balance, GenericAddress, UncheckedExtrinsic};
+
+pub type ComposedSystemRemark = (CallIndex, Vec<u8>);
+
+/// A call of a transfer with memo batch, see
+/// [`Api::balance_transfer_keep_alive_with_memo`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MemoCall {
+    Transfer(ComposedTransfer),
+    Remark(ComposedSystemRemark),
+}
+
+impl Encode for MemoCall {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        match self {
+            MemoCall::Transfer(call) => call.encode_to(output),
+            MemoCall::Remark(call) => call.encode_to(output),
+        }
+    }
+}
+
+impl MemoCall {
+    /// Decodes a remark if its call index is one of `remarks`, a transfer if
+    /// it is one of `transfers`. Any other call can not be decoded.
+    pub fn decode_with<I: Input>(
+        input: &mut I,
+        transfers: &[CallIndex],
+        remarks: &[CallIndex],
+    ) -> std::result::Result<Self, Error> {
+        let call_index = CallIndex::decode(input)?;
+        if remarks.contains(&call_index) {
+            Ok(MemoCall::Remark((call_index, Decode::decode(input)?)))
+        } else if transfers.contains(&call_index) {
+            Ok(MemoCall::Transfer((
+                call_index,
+                Decode::decode(input)?,
+                Decode::decode(input)?,
+            )))
+        } else {
+            Err("Not a transfer or remark".into())
+        }
+    }
+}
+
+/// The encoded call of an extrinsic, to be decoded once the call indices of
+/// its runtime are known
+struct RawCall(Vec<u8>);
+
+impl Encode for RawCall {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        output.write(&self.0);
+    }
+}
+
+impl Decode for RawCall {
+    fn decode<I: Input>(input: &mut I) -> std::result::Result<Self, Error> {
+        let len = input.remaining_len()?.ok_or("Unknown call length")?;
+        let mut call = vec![0; len];
+        input.read(&mut call)?;
+        Ok(RawCall(call))
+    }
+}
+
+/// A transfer paired with the remark of the same batch
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransferWithMemo {
+    pub from: Option<GenericAddress>,
+    pub to: GenericAddress,
+    pub amount: Balance,
+    pub memo: Vec<u8>,
+}
+
+/// Expects a `utility` batch of transfers and remarks and pairs each transfer
+/// with the remark right after it. Transfers without a remark right after
+/// them are left out. Call indices are those of runtime `spec_version`.
+pub fn decode_transfers_with_memo<N: SubstrateNetwork>(
+    xt_str: &str,
+    spec_version: u32,
+) -> Result<Vec<TransferWithMemo>> {
+    let indices = |indices: &[CallIndex]| -> Result<Vec<CallIndex>> {
+        indices
+            .iter()
+            .map(|i| call_index::<N>(spec_version, *i))
+            .collect()
+    };
+    let batches = indices(&[
+        [N::UTILITY_PALLET_IDX, N::UTILITY_BATCH],
+        [N::UTILITY_PALLET_IDX, N::UTILITY_BATCH_ALL],
+        [N::UTILITY_PALLET_IDX, N::UTILITY_FORCE_BATCH],
+    ])?;
+    let transfers = indices(&[
+        [N::BALANCE_PALLET_IDX, N::BALANCE_TRANSFER],
+        [N::BALANCE_PALLET_IDX, N::BALANCE_TRANSFER_KEEP_ALIVE],
+    ])?;
+    let remarks = indices(&[
+        [N::SYSTEM_PALLET_IDX, N::SYSTEM_REMARK],
+        [N::SYSTEM_PALLET_IDX, N::SYSTEM_REMARK_WITH_EVENT],
+    ])?;
+
+    let Ok(data) = hex::decode(xt_str.trim_start_matches("0x")) else {
+        return Ok(Vec::new());
+    };
+    let Ok(xt) = UncheckedExtrinsic::<RawCall>::decode_for::<N, _>(&mut data.as_slice()) else {
+        return Ok(Vec::new());
+    };
+    let call = &mut xt.function.0.as_slice();
+    let Ok(batch_index) = CallIndex::decode(call) else {
+        return Ok(Vec::new());
+    };
+    if !batches.contains(&batch_index) {
+        return Ok(Vec::new());
+    }
+    // any call other than transfers and remarks can not be decoded
+    let Ok(Compact(len)) = Compact::<u32>::decode(call) else {
+        return Ok(Vec::new());
+    };
+    let Ok(calls) = (0..len)
+        .map(|_| MemoCall::decode_with(call, &transfers, &remarks))
+        .collect::<std::result::Result<Vec<_>, _>>()
+    else {
+        return Ok(Vec::new());
+    };
+    let from = xt.signature.map(|(address, ..)| address);
+    Ok(calls
+        .iter()
+        .zip(calls.iter().skip(1))
+        .filter_map(|pair| match pair {
+            (MemoCall::Transfer((_, to, amount)), MemoCall::Remark((_, memo))) => {
+                Some(TransferWithMemo {
+                    from: from.clone(),
+                    to: to.clone(),
+                    amount: amount.0,
+                    memo: memo.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
+    /// Make some on-chain remark
+    pub fn system_remark(
+        &self,
+        remark: Vec<u8>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedSystemRemark>> {
+        let call = (
+            self.call_index([N::SYSTEM_PALLET_IDX, N::SYSTEM_REMARK])?,
+            remark,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Make some on-chain remark and emit an event
+    pub fn system_remark_with_event(
+        &self,
+        remark: Vec<u8>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedSystemRemark>> {
+        let call = (
+            self.call_index([N::SYSTEM_PALLET_IDX, N::SYSTEM_REMARK_WITH_EVENT])?,
+            remark,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Transfers `amount` to `to` and remarks `memo` in one
+    /// `utility.batch_all`, so the recipient can match the deposit with
+    /// [`decode_transfers_with_memo`]
+    pub fn balance_transfer_keep_alive_with_memo(
+        &self,
+        to: GenericAddress,
+        amount: Balance,
+        memo: Vec<u8>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedUtilityBatch<MemoCall>>> {
+        let transfer = (
+            self.call_index([N::BALANCE_PALLET_IDX, N::BALANCE_TRANSFER_KEEP_ALIVE])?,
+            to,
+            Compact(amount),
+        );
+        let remark = (
+            self.call_index([N::SYSTEM_PALLET_IDX, N::SYSTEM_REMARK_WITH_EVENT])?,
+            memo,
+        );
+        self.utility_batch_all(
+            vec![MemoCall::Transfer(transfer), MemoCall::Remark(remark)],
+            nonce,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp_core::crypto::AccountId32;
+
+    use super::*;
+    use crate::network::{HasStaking, Polkadot};
+
+    fn memo_batch(calls: Vec<MemoCall>) -> String {
+        let xt = UncheckedExtrinsic {
+            signature: None,
+            function: (
+                [Polkadot::UTILITY_PALLET_IDX, Polkadot::UTILITY_BATCH_ALL],
+                calls,
+            ),
+        };
+        xt.as_hex()
+    }
+
+    #[test]
+    fn transfers_with_memo() {
+        let to: GenericAddress = AccountId32::new([1; 32]).into();
+        let transfer = |amount| {
+            MemoCall::Transfer((
+                [
+                    Polkadot::BALANCE_PALLET_IDX,
+                    Polkadot::BALANCE_TRANSFER_KEEP_ALIVE,
+                ],
+                to.clone(),
+                Compact(amount),
+            ))
+        };
+        let remark = |memo: &[u8]| MemoCall::Remark(([0, 7], memo.to_vec()));
+
+        let xt = memo_batch(vec![transfer(10), remark(b"a"), transfer(20), remark(b"b")]);
+        let decoded = decode_transfers_with_memo::<Polkadot>(&xt, 1_002_000).unwrap();
+        assert_eq!(
+            decoded
+                .iter()
+                .map(|t| (t.amount, t.memo.as_slice()))
+                .collect::<Vec<_>>(),
+            [(10, b"a".as_slice()), (20, b"b")]
+        );
+        assert_eq!(decoded[0].to, to);
+        assert_eq!(decoded[0].from, None);
+
+        // a transfer without memo
+        let xt = memo_batch(vec![remark(b"a"), transfer(10)]);
+        assert!(decode_transfers_with_memo::<Polkadot>(&xt, 1_002_000)
+            .unwrap()
+            .is_empty());
+
+        // only the transfer right before a remark gets its memo
+        let xt = memo_batch(vec![transfer(10), transfer(20), remark(b"b")]);
+        let decoded = decode_transfers_with_memo::<Polkadot>(&xt, 1_002_000).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(
+            (decoded[0].amount, decoded[0].memo.as_slice()),
+            (20, b"b".as_slice())
+        );
+
+        // a remark of a pallet other than System
+        let xt = memo_batch(vec![
+            transfer(10),
+            MemoCall::Remark(([Polkadot::UTILITY_PALLET_IDX, 0], b"a".to_vec())),
+        ]);
+        assert!(decode_transfers_with_memo::<Polkadot>(&xt, 1_002_000)
+            .unwrap()
+            .is_empty());
+
+        // a call other than transfers and remarks
+        let bond = MemoCall::Transfer(([Polkadot::STAKING_PALLET_IDX, 0], to.clone(), Compact(1)));
+        let xt = memo_batch(vec![transfer(10), remark(b"a"), bond]);
+        assert!(decode_transfers_with_memo::<Polkadot>(&xt, 1_002_000)
+            .unwrap()
+            .is_empty());
+    }
+}
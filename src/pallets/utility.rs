@@ -0,0 +1,52 @@
+use parity_scale_codec::Encode;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::SubstrateNetwork;
+use crate::pallets::CallIndex;
+use crate::rpc::RpcClient;
+use crate::UncheckedExtrinsic;
+
+pub type ComposedUtilityBatch<Call> = (CallIndex, Vec<Call>);
+
+impl<S: Signer, Client: RpcClient, N: SubstrateNetwork> Api<'_, S, Client, N> {
+    /// Send a batch of dispatch calls, stopping at the first one that fails.
+    /// Calls dispatched before the failure are not reverted.
+    pub fn utility_batch<Call: Encode + Clone>(
+        &self,
+        calls: Vec<Call>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedUtilityBatch<Call>>> {
+        let call = (
+            self.call_index([N::UTILITY_PALLET_IDX, N::UTILITY_BATCH])?,
+            calls,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Send a batch of dispatch calls and atomically execute them. The whole
+    /// transaction will rollback and fail if any of the calls failed.
+    pub fn utility_batch_all<Call: Encode + Clone>(
+        &self,
+        calls: Vec<Call>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedUtilityBatch<Call>>> {
+        let call = (
+            self.call_index([N::UTILITY_PALLET_IDX, N::UTILITY_BATCH_ALL])?,
+            calls,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Send a batch of dispatch calls, continuing past the ones that fail.
+    pub fn utility_force_batch<Call: Encode + Clone>(
+        &self,
+        calls: Vec<Call>,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedUtilityBatch<Call>>> {
+        let call = (
+            self.call_index([N::UTILITY_PALLET_IDX, N::UTILITY_FORCE_BATCH])?,
+            calls,
+        );
+        self._create_xt(call, nonce)
+    }
+}
@@ -0,0 +1,278 @@
+use std::cmp::Reverse;
+
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result};
+use crate::network::{HasIdentity, HasSubstrateStaking};
+use crate::pallets::identity::IdentityJudgements;
+use crate::pallets::staking::{PagedExposureMetadata, ValidatorPrefs};
+use crate::pallets::storage::storage_value_key;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{GenericAddress, Perbill, H256};
+
+/// A validator of the current session and what nominators look at before
+/// nominating it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidatorCandidate {
+    pub stash: AccountId32,
+    pub prefs: ValidatorPrefs,
+    /// Exposure in the active era
+    pub exposure: Option<PagedExposureMetadata>,
+    /// Identity of the stash, `None` if it has none or it was not looked up,
+    /// see [`Api::identity_fill_candidates`]
+    pub identity: Option<IdentityJudgements>,
+    /// Average reward points over the eras looked at
+    pub avg_era_points: u32,
+}
+
+impl ValidatorCandidate {
+    /// Whether more than `max_nominators` nominators back the validator.
+    /// Exposures are paged, so every nominator is rewarded however many
+    /// there are.
+    pub fn is_oversubscribed(&self, max_nominators: u32) -> bool {
+        self.exposure
+            .map(|e| e.nominator_count > max_nominators)
+            .unwrap_or_default()
+    }
+
+    /// Whether any registrar vouched for the validator's identity
+    pub fn has_verified_identity(&self) -> bool {
+        self.identity
+            .as_ref()
+            .map(|i| i.is_verified())
+            .unwrap_or_default()
+    }
+}
+
+/// Criteria to filter validator candidates by
+#[derive(Debug, Clone)]
+pub struct ValidatorSelection {
+    /// Highest commission accepted
+    pub max_commission: Perbill,
+    /// Skip validators with more nominators than this, e.g. to spread stake
+    /// over less popular validators. Not set by default.
+    pub max_nominators: Option<u32>,
+    /// Skip validators without a verified identity. Off by default, as
+    /// identities of networks whose identity pallet moved to the People chain
+    /// have to be filled in from there.
+    pub require_verified_identity: bool,
+    /// Skip validators averaging fewer era points than this
+    pub min_avg_era_points: u32,
+    /// Number of past eras to average era points over
+    pub era_points_depth: u32,
+    /// Validators never to select
+    pub exclude: Vec<AccountId32>,
+    /// Number of validators to select, capped at `STAKING_MAX_NOMINATIONS`
+    pub max_targets: u32,
+}
+
+impl Default for ValidatorSelection {
+    fn default() -> Self {
+        Self {
+            max_commission: Perbill::from_percent(10),
+            max_nominators: None,
+            require_verified_identity: false,
+            min_avg_era_points: 0,
+            era_points_depth: 7,
+            exclude: Vec::new(),
+            max_targets: u32::MAX,
+        }
+    }
+}
+
+impl ValidatorSelection {
+    fn accepts(&self, c: &ValidatorCandidate) -> bool {
+        !c.prefs.blocked
+            && c.prefs.commission <= self.max_commission
+            && !self
+                .max_nominators
+                .map(|max| c.is_oversubscribed(max))
+                .unwrap_or_default()
+            && (!self.require_verified_identity || c.has_verified_identity())
+            && c.avg_era_points >= self.min_avg_era_points
+            && !self.exclude.contains(&c.stash)
+    }
+}
+
+/// Filters `candidates` by `selection` and ranks them by average era points,
+/// then by lowest commission, then by lowest total stake. Returns at most
+/// `max_targets` stashes, capped at `max_nominations`.
+pub fn select_validators(
+    mut candidates: Vec<ValidatorCandidate>,
+    selection: &ValidatorSelection,
+    max_nominations: u32,
+) -> Vec<AccountId32> {
+    candidates.retain(|c| selection.accepts(c));
+    candidates.sort_by_key(|c| {
+        (
+            Reverse(c.avg_era_points),
+            c.prefs.commission,
+            c.exposure.map(|e| e.total).unwrap_or_default(),
+        )
+    });
+    candidates
+        .into_iter()
+        .take(selection.max_targets.min(max_nominations) as usize)
+        .map(|c| c.stash)
+        .collect()
+}
+
+impl<S, Client: RpcClient, N: HasSubstrateStaking> Api<'_, S, Client, N> {
+    /// Returns the validator set of the current session
+    pub fn session_validators(&self, at_block: Option<H256>) -> Result<Vec<AccountId32>> {
+        let storage_key = storage_value_key("Session", "Validators");
+        let json_req = state_get_storage(storage_key, at_block);
+        Ok(self
+            .client
+            .post(json_req)?
+            .decode_into()?
+            .unwrap_or_default())
+    }
+
+    /// Returns the current validators with their preferences, exposure in
+    /// the active era and era points averaged over the last
+    /// `era_points_depth` eras. Validators that chilled are left out.
+    /// Identities live on another chain and are left empty.
+    pub fn staking_validator_candidates(
+        &self,
+        era_points_depth: u32,
+        at_block: Option<H256>,
+    ) -> Result<Vec<ValidatorCandidate>> {
+        let active_era = self
+            .staking_active_era(at_block)?
+            .map(|e| e.index)
+            .unwrap_or_default();
+        let mut era_points = Vec::new();
+        for era in active_era.saturating_sub(era_points_depth)..active_era {
+            era_points.extend(self.staking_eras_reward_points(era, at_block)?);
+        }
+
+        let mut candidates = Vec::new();
+        for stash in self.session_validators(at_block)? {
+            let Some(prefs) = self.staking_validators(stash.clone(), at_block)? else {
+                continue;
+            };
+            let exposure =
+                self.staking_eras_stakers_overview(active_era, stash.clone(), at_block)?;
+            let avg_era_points = if era_points.is_empty() {
+                0
+            } else {
+                let total: u64 = era_points.iter().map(|p| p.points_of(&stash) as u64).sum();
+                (total / era_points.len() as u64) as u32
+            };
+            candidates.push(ValidatorCandidate {
+                stash,
+                prefs,
+                exposure,
+                identity: None,
+                avg_era_points,
+            });
+        }
+        Ok(candidates)
+    }
+
+    /// Selects up to `STAKING_MAX_NOMINATIONS` validators matching
+    /// `selection`, ready to be passed to `staking_nominate`. Identities are
+    /// not looked up, to require verified identities fill them in with
+    /// [`Api::identity_fill_candidates`] and use [`select_validators`].
+    pub fn staking_select_validators(
+        &self,
+        selection: &ValidatorSelection,
+        at_block: Option<H256>,
+    ) -> Result<Vec<GenericAddress>> {
+        let candidates = self.staking_validator_candidates(selection.era_points_depth, at_block)?;
+        Ok(
+            select_validators(candidates, selection, N::STAKING_MAX_NOMINATIONS)
+                .into_iter()
+                .map(GenericAddress::from)
+                .collect(),
+        )
+    }
+}
+
+impl<S, Client: RpcClient, N: HasIdentity> Api<'_, S, Client, N> {
+    /// Fills in the identities of validator `candidates` of a relay chain from
+    /// this network, e.g. its People chain
+    pub fn identity_fill_candidates(
+        &self,
+        candidates: &mut [ValidatorCandidate],
+        at_block: Option<H256>,
+    ) -> Result<()> {
+        for candidate in candidates {
+            candidate.identity = self.identity_judgements(candidate.stash.clone(), at_block)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pallets::identity::Judgement;
+
+    fn candidate(
+        n: u8,
+        commission: u32,
+        nominator_count: u32,
+        verified: bool,
+        avg_era_points: u32,
+    ) -> ValidatorCandidate {
+        ValidatorCandidate {
+            stash: AccountId32::new([n; 32]),
+            prefs: ValidatorPrefs {
+                commission: Perbill::from_percent(commission),
+                blocked: false,
+            },
+            exposure: Some(PagedExposureMetadata {
+                total: 1_000,
+                own: 100,
+                nominator_count,
+                page_count: 1,
+            }),
+            identity: verified.then(|| IdentityJudgements {
+                judgements: vec![(0, Judgement::Reasonable)],
+                deposit: 0,
+            }),
+            avg_era_points,
+        }
+    }
+
+    #[test]
+    fn filter_and_rank() {
+        let mut blocked = candidate(6, 1, 10, true, 90_000);
+        blocked.prefs.blocked = true;
+        let candidates = vec![
+            candidate(1, 5, 10, true, 60_000),
+            candidate(2, 3, 10, true, 80_000),
+            // too much commission
+            candidate(3, 20, 10, true, 90_000),
+            // too many nominators
+            candidate(4, 1, 600, true, 90_000),
+            // no identity
+            candidate(5, 1, 10, false, 90_000),
+            blocked,
+            candidate(7, 1, 10, true, 60_000),
+        ];
+        let selection = ValidatorSelection {
+            max_nominators: Some(512),
+            require_verified_identity: true,
+            ..Default::default()
+        };
+        let selected = select_validators(candidates.clone(), &selection, 16);
+        assert_eq!(
+            selected,
+            vec![
+                AccountId32::new([2; 32]),
+                AccountId32::new([7; 32]),
+                AccountId32::new([1; 32])
+            ]
+        );
+
+        let selection = ValidatorSelection::default();
+        let selected = select_validators(candidates, &selection, 2);
+        assert_eq!(
+            selected,
+            vec![AccountId32::new([4; 32]), AccountId32::new([5; 32])]
+        );
+    }
+}
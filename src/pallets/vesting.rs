@@ -0,0 +1,195 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasVesting;
+use crate::pallets::storage::storage_vesting_vesting;
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, GenericAddress, UncheckedExtrinsic, H256};
+
+pub type ComposedVestingVest = CallIndex;
+pub type ComposedVestingVestOther = (CallIndex, GenericAddress);
+pub type ComposedVestingVestedTransfer = (CallIndex, GenericAddress, VestingInfo);
+pub type ComposedVestingMergeSchedules = (CallIndex, u32, u32);
+
+/// Struct to encode the vesting schedule of an individual account.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/vesting/src/vesting_info.rs
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub struct VestingInfo {
+    /// Locked amount at genesis.
+    pub locked: Balance,
+    /// Amount that gets unlocked every block after `starting_block`.
+    pub per_block: Balance,
+    /// Starting block for unlocking(vesting).
+    pub starting_block: u32,
+}
+
+impl VestingInfo {
+    /// Amount still locked at block `n`
+    pub fn locked_at(&self, n: u32) -> Balance {
+        let vested_blocks = n.saturating_sub(self.starting_block) as Balance;
+        // like `ending_block`, a `per_block` of 0 unlocks 1 per block
+        self.locked
+            .saturating_sub(self.per_block.max(1).saturating_mul(vested_blocks))
+    }
+
+    /// Block at which the schedule is fully unlocked
+    pub fn ending_block(&self) -> u32 {
+        // a `per_block` of 0 would never unlock, the pallet treats it as 1
+        let duration = self.locked.div_ceil(self.per_block.max(1));
+        self.starting_block
+            .saturating_add(duration.try_into().unwrap_or(u32::MAX))
+    }
+}
+
+/// Amount locked by all `schedules` at block `n`
+pub fn vesting_locked_at(schedules: &[VestingInfo], n: u32) -> Balance {
+    schedules
+        .iter()
+        .fold(0, |locked, s| locked.saturating_add(s.locked_at(n)))
+}
+
+impl<S: Signer, Client: RpcClient, N: HasVesting> Api<'_, S, Client, N> {
+    /// Unlocks the vested funds of the signer
+    pub fn vesting_vest(
+        &self,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedVestingVest>> {
+        self._create_xt(
+            self.call_index([N::VESTING_PALLET_IDX, N::VESTING_VEST])?,
+            nonce,
+        )
+    }
+
+    /// Unlocks the vested funds of `target`
+    pub fn vesting_vest_other(
+        &self,
+        target: GenericAddress,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedVestingVestOther>> {
+        let call = (
+            self.call_index([N::VESTING_PALLET_IDX, N::VESTING_VEST_OTHER])?,
+            target,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Transfers `schedule.locked` to `target`, locked by `schedule`
+    pub fn vesting_vested_transfer(
+        &self,
+        target: GenericAddress,
+        schedule: VestingInfo,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedVestingVestedTransfer>> {
+        let call = (
+            self.call_index([N::VESTING_PALLET_IDX, N::VESTING_VESTED_TRANSFER])?,
+            target,
+            schedule,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Merges the signer's schedules at `schedule1_index` and
+    /// `schedule2_index` into one, after vesting both
+    pub fn vesting_merge_schedules(
+        &self,
+        schedule1_index: u32,
+        schedule2_index: u32,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedVestingMergeSchedules>> {
+        let call = (
+            self.call_index([N::VESTING_PALLET_IDX, N::VESTING_MERGE_SCHEDULES])?,
+            schedule1_index,
+            schedule2_index,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasVesting> Api<'_, S, Client, N> {
+    /// Returns the vesting schedules of `account`
+    pub fn vesting_vesting<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<Vec<VestingInfo>>> {
+        let storage_key = storage_vesting_vesting(account.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the amount of `account` still locked by vesting at `at_block`.
+    ///
+    /// The vesting lock on the account is only lowered to this amount once
+    /// `vest` or `vest_other` is called.
+    pub fn vesting_locked<A: Into<AccountId32>>(
+        &self,
+        account: A,
+        at_block: Option<H256>,
+    ) -> Result<Balance> {
+        let Some(schedules) = self.vesting_vesting(account, at_block)? else {
+            return Ok(0);
+        };
+        let n = self.block_number(at_block)?;
+        Ok(vesting_locked_at(&schedules, n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vesting_info_decode() {
+        let schedule = VestingInfo {
+            locked: 1_000,
+            per_block: 10,
+            starting_block: 100,
+        };
+        let encoded = hex::decode(concat!(
+            "e8030000000000000000000000000000",
+            "0a000000000000000000000000000000",
+            "64000000"
+        ))
+        .unwrap();
+        assert_eq!(
+            VestingInfo::decode(&mut encoded.as_slice()).unwrap(),
+            schedule
+        );
+    }
+
+    #[test]
+    fn locked_over_time() {
+        let schedules = [
+            VestingInfo {
+                locked: 1_000,
+                per_block: 10,
+                starting_block: 100,
+            },
+            VestingInfo {
+                locked: 500,
+                per_block: 3,
+                starting_block: 150,
+            },
+        ];
+        assert_eq!(vesting_locked_at(&schedules, 0), 1_500);
+        assert_eq!(vesting_locked_at(&schedules, 150), 1_000);
+        assert_eq!(vesting_locked_at(&schedules, 200), 350);
+        assert_eq!(vesting_locked_at(&schedules, 400), 0);
+        assert_eq!(schedules[0].ending_block(), 200);
+        assert_eq!(schedules[1].ending_block(), 317);
+    }
+
+    #[test]
+    fn locked_with_zero_per_block() {
+        let schedule = VestingInfo {
+            locked: 100,
+            per_block: 0,
+            starting_block: 10,
+        };
+        assert_eq!(schedule.locked_at(10), 100);
+        assert_eq!(schedule.locked_at(60), 50);
+        assert_eq!(schedule.locked_at(schedule.ending_block()), 0);
+    }
+}
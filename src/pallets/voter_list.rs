@@ -0,0 +1,245 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasVoterList;
+use crate::pallets::storage::{storage_voter_list_list_bags, storage_voter_list_list_nodes};
+use crate::pallets::CallIndex;
+use crate::rpc::{state_get_storage, RpcClient};
+use crate::{Balance, GenericAddress, UncheckedExtrinsic, H256};
+
+pub mod bag_thresholds;
+
+pub type ComposedVoterListRebag = (CallIndex, GenericAddress);
+pub type ComposedVoterListPutInFrontOf = (CallIndex, GenericAddress);
+
+/// The score of a voter, its active stake converted to a vote weight.
+pub type Score = u64;
+
+/// A Node is the fundamental element comprising the doubly-linked list
+/// described by `Bag`.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/substrate/frame/bags-list/src/list/mod.rs
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct ListNode {
+    pub id: AccountId32,
+    pub prev: Option<AccountId32>,
+    pub next: Option<AccountId32>,
+    /// The upper threshold of the bag the node is in.
+    pub bag_upper: Score,
+    /// The score of the node when it was last inserted or rebagged.
+    pub score: Score,
+}
+
+/// A Bag is a doubly-linked list of ids, where each id is mapped to a
+/// `ListNode`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Encode, Decode)]
+pub struct ListBag {
+    pub head: Option<AccountId32>,
+    pub tail: Option<AccountId32>,
+}
+
+/// What a voter should do to be ranked correctly in the voter list
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum VoterListAction {
+    /// The voter is in the right bag and position
+    None,
+    /// The voter is in the wrong bag and has to be rebagged
+    Rebag,
+    /// The voter is in the right bag, but behind a lighter voter it can be
+    /// put in front of
+    PutInFrontOf(AccountId32),
+}
+
+/// Converts a stake to a score like `U128CurrencyToVote` does
+pub fn currency_to_vote(stake: Balance, total_issuance: Balance) -> Score {
+    let factor = (total_issuance / u64::MAX as Balance).max(1);
+    (stake / factor).min(u64::MAX as Balance) as Score
+}
+
+/// The upper threshold of the bag a node with `score` belongs in, given the
+/// runtime's bag thresholds
+pub fn notional_bag_for(score: Score, thresholds: &[Score]) -> Score {
+    thresholds
+        .iter()
+        .copied()
+        .find(|t| *t >= score)
+        .unwrap_or(Score::MAX)
+}
+
+/// Whether a node with `score` in the bag with `bag_upper` is in the wrong
+/// bag, given the runtime's bag thresholds
+pub fn needs_rebag(score: Score, bag_upper: Score, thresholds: &[Score]) -> bool {
+    notional_bag_for(score, thresholds) != bag_upper
+}
+
+impl<S: Signer, Client: RpcClient, N: HasVoterList> Api<'_, S, Client, N> {
+    /// Move `dislocated` to the bag that matches its current score. Can be
+    /// signed by anyone.
+    pub fn voter_list_rebag(
+        &self,
+        dislocated: GenericAddress,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedVoterListRebag>> {
+        let call = (
+            self.call_index([N::VOTER_LIST_PALLET_IDX, N::VOTER_LIST_REBAG])?,
+            dislocated,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Move the signer in front of `lighter` in their bag, if the signer has a
+    /// higher score
+    pub fn voter_list_put_in_front_of(
+        &self,
+        lighter: GenericAddress,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedVoterListPutInFrontOf>> {
+        let call = (
+            self.call_index([N::VOTER_LIST_PALLET_IDX, N::VOTER_LIST_PUT_IN_FRONT_OF])?,
+            lighter,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+impl<S, Client: RpcClient, N: HasVoterList> Api<'_, S, Client, N> {
+    /// Returns the voter list node of `voter`
+    pub fn voter_list_list_nodes<A: Into<AccountId32>>(
+        &self,
+        voter: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<ListNode>> {
+        let storage_key = storage_voter_list_list_nodes(voter.into().as_ref());
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the bag with upper threshold `bag_upper`, `None` if it is
+    /// empty
+    pub fn voter_list_list_bags(
+        &self,
+        bag_upper: Score,
+        at_block: Option<H256>,
+    ) -> Result<Option<ListBag>> {
+        let storage_key = storage_voter_list_list_bags(bag_upper);
+        let json_req = state_get_storage(storage_key, at_block);
+        self.client.post(json_req)?.decode_into()
+    }
+
+    /// Returns the current score of a staker, `None` if it does not stake
+    pub fn voter_list_score<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<Score>> {
+        let total_issuance = self.balance_total_issuance(at_block)?;
+        self.score_with(stash.into(), total_issuance, at_block)
+    }
+
+    fn score_with(
+        &self,
+        stash: AccountId32,
+        total_issuance: Balance,
+        at_block: Option<H256>,
+    ) -> Result<Option<Score>> {
+        Ok(self
+            .staking_stash_ledger(stash, at_block)?
+            .map(|ledger| currency_to_vote(ledger.active, total_issuance)))
+    }
+
+    /// Checks whether `stash` needs a `rebag` or a `put_in_front_of` to be
+    /// ranked by its current score. Returns `None` if it is not in the voter
+    /// list.
+    pub fn voter_list_action<A: Into<AccountId32>>(
+        &self,
+        stash: A,
+        at_block: Option<H256>,
+    ) -> Result<Option<VoterListAction>> {
+        let stash = stash.into();
+        let Some(node) = self.voter_list_list_nodes(stash.clone(), at_block)? else {
+            return Ok(None);
+        };
+        let total_issuance = self.balance_total_issuance(at_block)?;
+        let score = self
+            .score_with(stash.clone(), total_issuance, at_block)?
+            .unwrap_or_default();
+        if needs_rebag(score, node.bag_upper, N::VOTER_LIST_BAG_THRESHOLDS) {
+            return Ok(Some(VoterListAction::Rebag));
+        }
+
+        // walk the bag from its head to find a lighter node ahead of `stash`
+        let mut next = self
+            .voter_list_list_bags(node.bag_upper, at_block)?
+            .and_then(|b| b.head);
+        while let Some(id) = next {
+            if id == stash {
+                break;
+            }
+            let Some(ahead) = self.voter_list_list_nodes(id, at_block)? else {
+                break;
+            };
+            if ahead.score < score
+                && self
+                    .score_with(ahead.id.clone(), total_issuance, at_block)?
+                    .is_some_and(|s| s < score)
+            {
+                return Ok(Some(VoterListAction::PutInFrontOf(ahead.id)));
+            }
+            next = ahead.next;
+        }
+        Ok(Some(VoterListAction::None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebag_check() {
+        let thresholds = [10_000, 11_131, 12_391, 13_793];
+        // right bag
+        assert!(!needs_rebag(12_000, 12_391, &thresholds));
+        assert!(!needs_rebag(12_391, 12_391, &thresholds));
+        // stake went up
+        assert!(needs_rebag(12_392, 12_391, &thresholds));
+        // stake went down
+        assert!(needs_rebag(11_131, 12_391, &thresholds));
+        assert!(needs_rebag(10_500, 12_391, &thresholds));
+        // above the last threshold
+        assert_eq!(notional_bag_for(14_000, &thresholds), Score::MAX);
+        assert!(!needs_rebag(14_000, Score::MAX, &thresholds));
+    }
+
+    #[test]
+    fn relay_bag_thresholds() {
+        use bag_thresholds::{KUSAMA_BAG_THRESHOLDS, POLKADOT_BAG_THRESHOLDS};
+
+        assert_eq!(
+            POLKADOT_BAG_THRESHOLDS[..4],
+            [
+                10_000_000_000,
+                11_131_723_507,
+                12_391_526_824,
+                13_793_905_044
+            ]
+        );
+        assert_eq!(
+            KUSAMA_BAG_THRESHOLDS[..4],
+            [33_333_333, 38_184_666, 43_742_062, 50_108_281]
+        );
+        for thresholds in [POLKADOT_BAG_THRESHOLDS, KUSAMA_BAG_THRESHOLDS] {
+            assert!(thresholds.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(thresholds[199], Score::MAX);
+        }
+    }
+
+    #[test]
+    fn score_from_stake() {
+        // total issuance below u64::MAX keeps the stake as is
+        assert_eq!(currency_to_vote(1_000, 15_000_000_000_000_000_000), 1_000);
+        let total_issuance = u64::MAX as Balance * 4;
+        assert_eq!(currency_to_vote(4_000, total_issuance), 1_000);
+        assert_eq!(currency_to_vote(total_issuance, total_issuance), u64::MAX);
+    }
+}
@@ -0,0 +1,419 @@
+//! The `BagThresholds` of the relay chain runtimes, as generated by
+//! substrate's `generate-bags`: each threshold is the previous one times a
+//! constant ratio, rounded, and the last bag is unbounded.
+
+use super::Score;
+
+/// Upper thresholds of the voter list bags of Polkadot: 200 bags, from an
+/// existential weight of 10_000_000_000 up by a ratio of 1.1131723507077667
+pub const POLKADOT_BAG_THRESHOLDS: [Score; 200] = [
+    10_000_000_000,
+    11_131_723_507,
+    12_391_526_824,
+    13_793_905_044,
+    15_354_993_703,
+    17_092_754_435,
+    19_027_181_634,
+    21_180_532_507,
+    23_577_583_160,
+    26_245_913_670,
+    29_216_225_417,
+    32_522_694_326,
+    36_203_364_094,
+    40_300_583_912,
+    44_861_495_728,
+    49_938_576_656,
+    55_590_242_767,
+    61_881_521_217,
+    68_884_798_439,
+    76_680_653_006,
+    85_358_782_760,
+    95_019_036_859,
+    105_772_564_622,
+    117_743_094_401,
+    131_068_357_174,
+    145_901_671_259,
+    162_413_706_368,
+    180_794_447_305,
+    201_255_379_901,
+    224_031_924_337,
+    249_386_143_848,
+    277_609_759_981,
+    309_027_509_097,
+    344_000_878_735,
+    382_932_266_827,
+    426_269_611_626,
+    474_511_545_609,
+    528_213_132_664,
+    587_992_254_562,
+    654_536_720_209,
+    728_612_179_460,
+    811_070_932_564,
+    902_861_736_593,
+    1_005_040_721_687,
+    1_118_783_542_717,
+    1_245_398_906_179,
+    1_386_343_627_960,
+    1_543_239_395_225,
+    1_717_891_425_287,
+    1_912_309_236_147,
+    2_128_729_767_682,
+    2_369_643_119_512,
+    2_637_821_201_686,
+    2_936_349_627_828,
+    3_268_663_217_709,
+    3_638_585_517_729,
+    4_050_372_794_022,
+    4_508_763_004_364,
+    5_019_030_312_352,
+    5_587_045_771_074,
+    6_219_344_874_498,
+    6_923_202_753_807,
+    7_706_717_883_882,
+    8_578_905_263_043,
+    9_549_800_138_161,
+    10_630_573_468_586,
+    11_833_660_457_397,
+    13_172_903_628_838,
+    14_663_712_098_160,
+    16_323_238_866_411,
+    18_170_578_180_087,
+    20_226_985_226_447,
+    22_516_120_692_255,
+    25_064_322_999_817,
+    27_900_911_352_605,
+    31_058_523_077_268,
+    34_573_489_143_434,
+    38_486_252_181_966,
+    42_841_831_811_331,
+    47_690_342_626_046,
+    53_087_570_807_094,
+    59_095_615_988_698,
+    65_783_605_766_662,
+    73_228_491_069_308,
+    81_515_931_542_404,
+    90_741_281_135_191,
+    101_010_685_227_495,
+    112_442_301_921_293,
+    125_167_661_548_718,
+    139_333_180_038_781,
+    155_101_843_555_358,
+    172_655_083_789_626,
+    192_194_865_483_744,
+    213_946_010_204_502,
+    238_158_783_103_893,
+    265_111_772_429_462,
+    295_115_094_915_607,
+    328_513_963_936_552,
+    365_692_661_475_578,
+    407_078_959_611_349,
+    453_149_042_394_237,
+    504_432_984_742_966,
+    561_520_851_400_862,
+    625_069_486_125_324,
+    695_810_069_225_823,
+    774_556_530_406_243,
+    862_214_913_708_369,
+    959_793_802_308_039,
+    1_068_415_923_109_985,
+    1_189_331_064_661_951,
+    1_323_930_457_019_515,
+    1_473_762_779_014_021,
+    1_640_551_977_100_649,
+    1_826_217_100_807_404,
+    2_032_894_383_008_501,
+    2_262_961_819_074_188,
+    2_519_066_527_700_738,
+    2_804_155_208_229_882,
+    3_121_508_044_894_685,
+    3_474_776_448_088_622,
+    3_868_025_066_902_796,
+    4_305_778_556_320_752,
+    4_793_073_637_166_665,
+    5_335_517_047_800_242,
+    5_939_350_054_341_159,
+    6_611_520_261_667_250,
+    7_359_761_551_432_161,
+    8_192_683_066_856_378,
+    9_119_868_268_136_230,
+    10_151_985_198_186_376,
+    11_300_909_227_415_580,
+    12_579_859_689_817_292,
+    14_003_551_982_487_792,
+    15_588_366_878_604_342,
+    17_352_539_001_951_086,
+    19_316_366_631_550_092,
+    21_502_445_250_375_680,
+    23_935_927_525_325_748,
+    26_644_812_709_737_600,
+    29_660_268_798_266_784,
+    33_016_991_140_790_860,
+    36_753_601_641_491_664,
+    40_913_093_136_236_104,
+    45_543_324_061_189_736,
+    50_697_569_104_240_168,
+    56_435_132_174_936_472,
+    62_822_028_745_677_552,
+    69_931_745_415_056_768,
+    77_846_085_432_775_824,
+    86_656_109_914_600_688,
+    96_463_185_576_826_656,
+    107_380_151_045_315_664,
+    119_532_615_158_469_088,
+    133_060_402_202_199_856,
+    148_119_160_705_543_712,
+    164_882_154_307_451_552,
+    183_542_255_300_186_560,
+    204_314_163_786_713_728,
+    227_436_877_985_347_776,
+    253_176_444_104_585_088,
+    281_829_017_427_734_464,
+    313_724_269_827_691_328,
+    349_229_182_918_168_832,
+    388_752_270_484_770_624,
+    432_748_278_778_513_664,
+    481_723_418_752_617_984,
+    536_241_190_443_833_600,
+    596_928_866_512_693_376,
+    664_484_709_541_257_600,
+    739_686_006_129_409_280,
+    823_398_010_228_713_984,
+    916_583_898_614_395_264,
+    1_020_315_853_041_475_584,
+    1_135_787_396_594_579_584,
+    1_264_327_126_171_442_688,
+    1_407_413_999_103_859_968,
+    1_566_694_349_801_462_272,
+    1_744_000_832_209_069_824,
+    1_941_373_506_026_471_680,
+    2_161_083_309_305_266_176,
+    2_405_658_187_494_662_656,
+    2_677_912_179_572_818_944,
+    2_980_977_795_924_034_048,
+    3_318_342_060_496_414_208,
+    3_693_886_631_935_247_360,
+    4_111_932_465_319_354_368,
+    4_577_289_528_371_127_808,
+    5_095_312_144_166_932_480,
+    5_671_960_597_112_134_656,
+    6_313_869_711_009_142_784,
+    7_028_425_188_266_614_784,
+    7_823_848_588_596_424_704,
+    8_709_291_924_949_524_480,
+    9_694_942_965_096_232_960,
+    10_792_142_450_433_898_496,
+    12_013_514_580_722_579_456,
+    13_373_112_266_084_982_784,
+    14_886_578_817_516_689_408,
+    16_571_327_936_291_497_984,
+    18_446_744_073_709_551_615,
+];
+
+/// Upper thresholds of the voter list bags of Kusama: 200 bags, from an
+/// existential weight of 33_333_333 up by a ratio of 1.1455399939091000
+pub const KUSAMA_BAG_THRESHOLDS: [Score; 200] = [
+    33_333_333,
+    38_184_666,
+    43_742_062,
+    50_108_281,
+    57_401_040,
+    65_755_187,
+    75_325_197,
+    86_288_026,
+    98_846_385,
+    113_232_487,
+    129_712_342,
+    148_590_675,
+    170_216_561,
+    194_989_878,
+    223_368_704,
+    255_877_784,
+    293_118_235,
+    335_778_661,
+    384_647_885,
+    440_629_536,
+    504_758_756,
+    578_221_342,
+    662_375_673,
+    758_777_824,
+    869_210_344,
+    995_715_212,
+    1_140_631_598,
+    1_306_639_114,
+    1_496_807_363,
+    1_714_652_697,
+    1_964_203_240,
+    2_250_073_368,
+    2_577_549_032,
+    2_952_685_502,
+    3_382_419_332,
+    3_874_696_621,
+    4_438_619_944,
+    5_084_616_664,
+    5_824_631_742,
+    6_672_348_610,
+    7_643_442_186,
+    8_755_868_715,
+    10_030_197_794,
+    11_489_992_720,
+    13_162_246_190,
+    15_077_879_420,
+    17_272_313_899,
+    19_786_126_359,
+    22_665_799_069,
+    25_964_579_327,
+    29_743_464_044,
+    34_072_327_620,
+    39_031_213_974,
+    44_711_816_618,
+    51_219_174_136,
+    58_673_612_428,
+    67_212_969_623,
+    76_995_144_813,
+    88_201_017_720,
+    101_037_793_302,
+    115_742_833_124,
+    132_588_044_352,
+    151_884_907_519,
+    173_990_236_034,
+    199_312_773_927,
+    228_320_753_830,
+    261_550_554_952,
+    299_616_621_127,
+    343_222_822_341,
+    393_175_469_814,
+    450_398_225_296,
+    515_949_180_262,
+    591_040_420_815,
+    677_060_440_060,
+    775_599_812_382,
+    888_480_604_352,
+    1_017_790_066_098,
+    1_165_919_226_119,
+    1_335_607_103_187,
+    1_529_991_352_850,
+    1_752_666_285_025,
+    2_007_749_325_472,
+    2_299_957_150_072,
+    2_634_692_899_685,
+    3_018_146_088_258,
+    3_457_407_051_560,
+    3_960_598_052_785,
+    4_537_023_469_264,
+    5_197_341_837_346,
+    5_953_762_936_697,
+    6_820_273_558_240,
+    7_812_896_130_365,
+    8_949_984_985_591,
+    10_252_565_745_880,
+    11_744_724_102_088,
+    13_454_051_176_370,
+    15_412_153_702_632,
+    17_655_238_458_639,
+    20_224_781_756_373,
+    23_168_296_370_008,
+    26_540_210_082_583,
+    30_402_872_096_348,
+    34_827_705_916_070,
+    39_896_530_022_963,
+    45_703_070_759_499,
+    52_354_695_399_464,
+    59_974_397_449_015,
+    68_703_070_888_447,
+    78_702_115_407_088,
+    90_156_420_804_069,
+    103_277_785_738_759,
+    118_308_834_046_123,
+    135_527_501_032_588,
+    155_252_172_707_386,
+    177_847_572_977_594,
+    203_731_507_665_501,
+    233_382_590_050_230,
+    267_349_090_784_630,
+    306_259_075_829_029,
+    350_832_019_859_793,
+    401_892_109_893_305,
+    460_383_485_119_292,
+    527_387_694_739_404,
+    604_143_696_619_511,
+    692_070_766_545_736,
+    792_794_741_693_469,
+    908_178_083_570_703,
+    1_040_354_316_321_961,
+    1_191_767_477_182_765,
+    1_365_217_308_553_008,
+    1_563_911_027_324_411,
+    1_791_522_628_715_580,
+    2_052_260_821_186_860,
+    2_350_946_848_602_280,
+    2_693_103_638_628_474,
+    3_085_057_925_791_037,
+    3_534_057_237_519_885,
+    4_048_403_906_342_940,
+    4_637_608_586_213_668,
+    5_312_566_111_603_995,
+    6_085_756_951_128_531,
+    6_971_477_980_728_040,
+    7_986_106_843_580_624,
+    9_148_404_784_952_770,
+    10_479_863_561_632_778,
+    12_005_102_840_561_012,
+    13_752_325_434_854_380,
+    15_753_838_794_879_048,
+    18_046_652_397_130_688,
+    20_673_162_077_088_732,
+    23_681_933_959_870_064,
+    27_128_602_484_145_260,
+    31_076_899_124_450_156,
+    35_599_830_833_736_348,
+    40_781_029_996_443_328,
+    46_716_300_853_732_512,
+    53_515_390_995_440_424,
+    61_304_020_674_959_928,
+    70_226_207_470_596_936,
+    80_446_929_278_126_800,
+    92_155_174_875_271_168,
+    105_567_438_465_310_176,
+    120_931_722_816_550_704,
+    138_532_125_018_688_464,
+    158_694_089_650_123_072,
+    181_790_426_491_212_160,
+    208_248_204_055_475_872,
+    238_556_646_405_290_848,
+    273_276_179_270_092_192,
+    313_048_792_736_563_520,
+    358_609_912_124_694_080,
+    410_801_996_551_064_960,
+    470_590_116_626_953_088,
+    539_079_799_334_522_496,
+    617_537_470_046_187_776,
+    707_413_869_675_350_912,
+    810_370_879_959_114_368,
+    928_312_252_892_475_904,
+    1_063_418_812_524_189_696,
+    1_218_188_780_021_782_528,
+    1_395_483_967_646_286_592,
+    1_598_582_695_797_773_824,
+    1_831_240_411_607_374_592,
+    2_097_759_129_958_809_600,
+    2_403_066_980_955_773_440,
+    2_752_809_334_727_236_096,
+    3_153_453_188_536_351_744,
+    3_612_406_746_388_564_480,
+    4_138_156_402_255_148_032,
+    4_740_423_659_834_265_600,
+    5_430_344_890_413_097_984,
+    6_220_677_252_688_132_096,
+    7_126_034_582_154_840_064,
+    8_163_157_611_837_691_904,
+    9_351_223_520_943_572_992,
+    10_712_200_535_224_332_288,
+    12_271_254_135_873_939_456,
+    14_057_212_388_066_050_048,
+    16_103_098_993_404_108_800,
+    18_446_744_073_709_551_615,
+];
+
+/// Upper thresholds of the voter list bags of Westend, generated from the same
+/// existential weight as Polkadot's
+pub const WESTEND_BAG_THRESHOLDS: [Score; 200] = POLKADOT_BAG_THRESHOLDS;
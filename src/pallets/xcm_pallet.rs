@@ -0,0 +1,332 @@
+use parity_scale_codec::{Decode, Encode, Error, Input, Output};
+use sp_core::crypto::AccountId32;
+
+use crate::client::{Api, Result, Signer};
+use crate::network::HasXcm;
+use crate::pallets::CallIndex;
+use crate::rpc::RpcClient;
+use crate::{Balance, UncheckedExtrinsic, Weight};
+
+pub type ParaId = u32;
+
+pub type ComposedXcmTransferAssets = (
+    CallIndex,
+    VersionedLocation,
+    VersionedLocation,
+    VersionedAssets,
+    u32,
+    WeightLimit,
+);
+
+/// Para id of Asset Hub on every relay chain
+pub const ASSET_HUB_PARA_ID: ParaId = 1000;
+
+/// XCM version to encode locations and assets with
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum XcmVersion {
+    V3,
+    V4,
+}
+
+/// A global identifier of a consensus system
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum NetworkId {
+    /// Network specified by the first 32 bytes of its genesis block.
+    ByGenesis([u8; 32]),
+    /// Network defined by the first 32-bytes of the hash and number of some
+    /// block it contains.
+    ByFork {
+        block_number: u64,
+        block_hash: [u8; 32],
+    },
+    Polkadot,
+    Kusama,
+    Westend,
+    Rococo,
+    Wococo,
+    Ethereum {
+        #[codec(compact)]
+        chain_id: u64,
+    },
+    BitcoinCore,
+    BitcoinCash,
+    PolkadotBulletin,
+}
+
+/// A single item in a path to describe the relative location of a consensus
+/// system. `Plurality` junctions are not supported.
+/// https://github.com/paritytech/polkadot-sdk/blob/master/polkadot/xcm/src/v4/junction.rs
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum Junction {
+    /// An indexed parachain belonging to and operated by the context.
+    Parachain(#[codec(compact)] ParaId),
+    /// A 32-byte identifier for an account of a specific network.
+    AccountId32 {
+        network: Option<NetworkId>,
+        id: [u8; 32],
+    },
+    /// An 8-byte index for an account of a specific network.
+    AccountIndex64 {
+        network: Option<NetworkId>,
+        #[codec(compact)]
+        index: u64,
+    },
+    /// A 20-byte identifier for an account of a specific network.
+    AccountKey20 {
+        network: Option<NetworkId>,
+        key: [u8; 20],
+    },
+    /// An instanced, indexed pallet that forms a constituent part of the
+    /// context.
+    PalletInstance(u8),
+    /// A non-descript index within the context location.
+    GeneralIndex(#[codec(compact)] u128),
+    /// A nondescript array datum, `length` bytes of `data` are used.
+    GeneralKey { length: u8, data: [u8; 32] },
+    /// The unambiguous child.
+    OnlyChild,
+    /// A global network capable of externalizing its own consensus.
+    #[codec(index = 9)]
+    GlobalConsensus(NetworkId),
+}
+
+/// A relative path between consensus systems, `MultiLocation` in XCM v3.
+/// Both versions encode the same way: the number of parents, then the
+/// number of junctions as the variant of `Junctions` followed by each
+/// junction.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Location {
+    pub parents: u8,
+    pub interior: Vec<Junction>,
+}
+
+impl Location {
+    /// Maximum number of junctions of a location
+    pub const MAX_JUNCTIONS: usize = 8;
+
+    pub fn new(parents: u8, interior: Vec<Junction>) -> Self {
+        Self { parents, interior }
+    }
+
+    /// The location of the context itself
+    pub fn here() -> Self {
+        Self::new(0, Vec::new())
+    }
+
+    /// The parent of the context, e.g. the relay chain of a parachain
+    pub fn parent() -> Self {
+        Self::new(1, Vec::new())
+    }
+
+    /// A parachain of the context, i.e. when the context is a relay chain
+    pub fn parachain(id: ParaId) -> Self {
+        Self::new(0, vec![Junction::Parachain(id)])
+    }
+}
+
+impl From<AccountId32> for Location {
+    /// An account of the context, e.g. the beneficiary of a transfer
+    fn from(account: AccountId32) -> Self {
+        Self::new(
+            0,
+            vec![Junction::AccountId32 {
+                network: None,
+                id: account.into(),
+            }],
+        )
+    }
+}
+
+impl Encode for Location {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        assert!(
+            self.interior.len() <= Self::MAX_JUNCTIONS,
+            "Location has more than 8 junctions"
+        );
+        output.push_byte(self.parents);
+        output.push_byte(self.interior.len() as u8);
+        for junction in &self.interior {
+            junction.encode_to(output);
+        }
+    }
+}
+
+impl Decode for Location {
+    fn decode<I: Input>(input: &mut I) -> std::result::Result<Self, Error> {
+        let parents = input.read_byte()?;
+        let len = input.read_byte()? as usize;
+        if len > Self::MAX_JUNCTIONS {
+            return Err("Invalid Junctions variant".into());
+        }
+        let interior = (0..len)
+            .map(|_| Junction::decode(input))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Self { parents, interior })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum VersionedLocation {
+    #[codec(index = 3)]
+    V3(Location),
+    #[codec(index = 4)]
+    V4(Location),
+}
+
+impl VersionedLocation {
+    pub fn new(version: XcmVersion, location: Location) -> Self {
+        match version {
+            XcmVersion::V3 => Self::V3(location),
+            XcmVersion::V4 => Self::V4(location),
+        }
+    }
+}
+
+/// A unique instance of a non-fungible asset
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum AssetInstance {
+    Undefined,
+    Index(#[codec(compact)] u128),
+    Array4([u8; 4]),
+    Array8([u8; 8]),
+    Array16([u8; 16]),
+    Array32([u8; 32]),
+}
+
+/// An amount of a fungible asset or an instance of a non-fungible one
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum Fungibility {
+    Fungible(#[codec(compact)] Balance),
+    NonFungible(AssetInstance),
+}
+
+/// The id of an asset in XCM v3
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum MultiAssetId {
+    Concrete(Location),
+    Abstract([u8; 32]),
+}
+
+/// An asset in XCM v3
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct MultiAsset {
+    pub id: MultiAssetId,
+    pub fun: Fungibility,
+}
+
+/// An asset in XCM v4, identified by its location
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct Asset {
+    pub id: Location,
+    pub fun: Fungibility,
+}
+
+/// Assets sorted by id, then fungibility
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub enum VersionedAssets {
+    #[codec(index = 3)]
+    V3(Vec<MultiAsset>),
+    #[codec(index = 4)]
+    V4(Vec<Asset>),
+}
+
+impl VersionedAssets {
+    /// `amount` of the fungible asset at `id`, e.g. [`Location::here`] for
+    /// the native token of a relay chain
+    pub fn fungible(version: XcmVersion, id: Location, amount: Balance) -> Self {
+        let fun = Fungibility::Fungible(amount);
+        match version {
+            XcmVersion::V3 => Self::V3(vec![MultiAsset {
+                id: MultiAssetId::Concrete(id),
+                fun,
+            }]),
+            XcmVersion::V4 => Self::V4(vec![Asset { id, fun }]),
+        }
+    }
+}
+
+/// Maximum weight the execution of an XCM message at the destination may
+/// take
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encode, Decode)]
+pub enum WeightLimit {
+    Unlimited,
+    Limited(Weight),
+}
+
+impl<S: Signer, Client: RpcClient, N: HasXcm> Api<'_, S, Client, N> {
+    /// Transfer `assets` to `beneficiary` on `dest`, where the assets are
+    /// reserved by the context or by `dest`. Fees are paid with
+    /// `assets[fee_asset_item]`.
+    pub fn xcm_limited_reserve_transfer_assets(
+        &self,
+        dest: VersionedLocation,
+        beneficiary: VersionedLocation,
+        assets: VersionedAssets,
+        fee_asset_item: u32,
+        weight_limit: WeightLimit,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedXcmTransferAssets>> {
+        let call = (
+            self.call_index([N::XCM_PALLET_IDX, N::XCM_LIMITED_RESERVE_TRANSFER_ASSETS])?,
+            dest,
+            beneficiary,
+            assets,
+            fee_asset_item,
+            weight_limit,
+        );
+        self._create_xt(call, nonce)
+    }
+
+    /// Teleport `assets` to `beneficiary` on `dest`, which has to trust the
+    /// context as a teleporter, e.g. a relay chain and its Asset Hub. Fees
+    /// are paid with `assets[fee_asset_item]`.
+    pub fn xcm_limited_teleport_assets(
+        &self,
+        dest: VersionedLocation,
+        beneficiary: VersionedLocation,
+        assets: VersionedAssets,
+        fee_asset_item: u32,
+        weight_limit: WeightLimit,
+        nonce: Option<u32>,
+    ) -> Result<UncheckedExtrinsic<ComposedXcmTransferAssets>> {
+        let call = (
+            self.call_index([N::XCM_PALLET_IDX, N::XCM_LIMITED_TELEPORT_ASSETS])?,
+            dest,
+            beneficiary,
+            assets,
+            fee_asset_item,
+            weight_limit,
+        );
+        self._create_xt(call, nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location_encode() {
+        let dest = VersionedLocation::new(XcmVersion::V3, Location::parachain(ASSET_HUB_PARA_ID));
+        assert_eq!(dest.encode(), [3, 0, 1, 0, 0xa1, 0x0f]);
+        let decoded = VersionedLocation::decode(&mut dest.encode().as_slice()).unwrap();
+        assert_eq!(decoded, dest);
+
+        let beneficiary =
+            VersionedLocation::new(XcmVersion::V4, Location::from(AccountId32::new([7; 32])));
+        assert_eq!(
+            beneficiary.encode(),
+            [[4, 0, 1, 1, 0].as_slice(), &[7; 32]].concat()
+        );
+    }
+
+    #[test]
+    fn assets_encode() {
+        let assets = VersionedAssets::fungible(XcmVersion::V3, Location::here(), 10);
+        assert_eq!(assets.encode(), [3, 4, 0, 0, 0, 0, 40]);
+        let assets = VersionedAssets::fungible(XcmVersion::V4, Location::parent(), 10);
+        assert_eq!(assets.encode(), [4, 4, 1, 0, 0, 40]);
+        let limit = WeightLimit::Limited(Weight::from_parts(1, 2));
+        assert_eq!(limit.encode(), [1, 4, 8]);
+    }
+}
@@ -106,6 +106,19 @@ pub fn state_get_runtime_version_with_id(id: u32) -> Value {
     json_req("state_getRuntimeVersion", vec![Value::Null], id)
 }
 
+pub(crate) fn state_get_runtime_version_at(at_block: Option<H256>) -> Value {
+    json_req(
+        "state_getRuntimeVersion",
+        vec![to_value(at_block).unwrap()],
+        1,
+    )
+}
+
+/// The SCALE encoded metadata of the runtime at `at_block`
+pub fn state_get_metadata(at_block: Option<H256>) -> Value {
+    json_req("state_getMetadata", vec![to_value(at_block).unwrap()], 1)
+}
+
 pub(crate) fn state_get_storage(key: StorageKey, at_block: Option<H256>) -> Value {
     json_req(
         "state_getStorage",
@@ -114,6 +127,32 @@ pub(crate) fn state_get_storage(key: StorageKey, at_block: Option<H256>) -> Valu
     )
 }
 
+pub(crate) fn state_get_keys_paged(
+    prefix: &StorageKey,
+    count: u32,
+    start_key: Option<&StorageKey>,
+    at_block: Option<H256>,
+) -> Value {
+    json_req(
+        "state_getKeysPaged",
+        vec![
+            to_value(prefix).unwrap(),
+            to_value(count).unwrap(),
+            to_value(start_key).unwrap(),
+            to_value(at_block).unwrap(),
+        ],
+        1,
+    )
+}
+
+pub(crate) fn state_query_storage_at(keys: &[StorageKey], at_block: Option<H256>) -> Value {
+    json_req(
+        "state_queryStorageAt",
+        vec![to_value(keys).unwrap(), to_value(at_block).unwrap()],
+        1,
+    )
+}
+
 pub fn payment_query_fee_details(xt_hex_prefixed: &str, at_block: Option<H256>) -> Value {
     json_req(
         "payment_queryFeeDetails",
@@ -7,7 +7,7 @@ use pdotc::ss58::Ss58Codec;
 use pdotc::AccountId32;
 use ureq::Agent;
 
-use crate::{get_balance, validate_xt, KeyStore, PDotClient};
+use crate::{get_balance, get_num_slashing_spans, validate_xt, KeyStore, PDotClient};
 
 static CLIENT: OnceLock<PDotClient<Agent>> = OnceLock::new();
 
@@ -17,6 +17,7 @@ static API: LazyLock<Api<KeyStore, PDotClient<Agent>, Kusama>> = LazyLock::new(|
 });
 
 get_balance!("GvWdZbtNY8nSFBfZ2Jr9V8hdo2F84jPdn8BBV55AQtgUsjq");
+get_num_slashing_spans!("GvWdZbtNY8nSFBfZ2Jr9V8hdo2F84jPdn8BBV55AQtgUsjq");
 
 validate_xt!(staking_rebond(), "0x0613a10f");
 validate_xt!(staking_bond_extra(), "0x0601a10f");
@@ -32,6 +33,21 @@ validate_xt!(
     staking_nominate("GvWdZbtNY8nSFBfZ2Jr9V8hdo2F84jPdn8BBV55AQtgUsjq"),
     "0x06050400c05740c342cde29bac622eba115bca3c9d2d194ef52aac55c58208cfbff8a931"
 );
+validate_xt!(staking_validate(), "0x060402c2eb0b00");
+validate_xt!(staking_set_payee(), "0x060701");
+validate_xt!(
+    staking_payout_stakers("GvWdZbtNY8nSFBfZ2Jr9V8hdo2F84jPdn8BBV55AQtgUsjq"),
+    "0x0612c05740c342cde29bac622eba115bca3c9d2d194ef52aac55c58208cfbff8a931e8030000"
+);
+validate_xt!(
+    staking_chill_other("GvWdZbtNY8nSFBfZ2Jr9V8hdo2F84jPdn8BBV55AQtgUsjq"),
+    "0x0617c05740c342cde29bac622eba115bca3c9d2d194ef52aac55c58208cfbff8a931"
+);
+validate_xt!(fast_unstake_register_fast_unstake(), "0x2a00");
+validate_xt!(vesting_vest(), "0x1c00");
+validate_xt!(conviction_voting_remove_vote(), "0x14040064000000");
+validate_xt!(system_remark_with_event(), "0x0007106d656d6f");
+validate_xt!(indices_free(), "0x030201000000");
 validate_xt!(
     proxy_add_proxy("GvWdZbtNY8nSFBfZ2Jr9V8hdo2F84jPdn8BBV55AQtgUsjq"),
     "0x1e0100c05740c342cde29bac622eba115bca3c9d2d194ef52aac55c58208cfbff8a9310300000000"
@@ -41,3 +57,10 @@ validate_xt!(
     "0x1e0200c05740c342cde29bac622eba115bca3c9d2d194ef52aac55c58208cfbff8a9310300000000"
 );
 validate_xt!(proxy_remove_proxies(), "0x1e03");
+
+#[cfg(feature = "metadata")]
+#[test]
+fn verify_metadata() {
+    let report = API.verify_metadata(None).unwrap();
+    assert!(report.is_ok(), "{report}");
+}
@@ -117,6 +117,22 @@ macro_rules! validate_xt {
     };
 }
 
+#[macro_export]
+macro_rules! get_num_slashing_spans {
+    ($addr:literal) => {
+        #[test]
+        fn get_num_slashing_spans() {
+            let spans = API
+                .staking_num_slashing_spans(
+                    AccountId32::from_ss58check_with_version($addr).unwrap().0,
+                    None,
+                )
+                .unwrap();
+            dbg!(spans);
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! get_balance {
     ($addr:literal) => {
@@ -7,7 +7,7 @@ use pdotc::ss58::Ss58Codec;
 use pdotc::AccountId32;
 use ureq::Agent;
 
-use crate::{get_balance, validate_xt, KeyStore, PDotClient};
+use crate::{get_balance, get_num_slashing_spans, validate_xt, KeyStore, PDotClient};
 
 static CLIENT: OnceLock<PDotClient<Agent>> = OnceLock::new();
 
@@ -17,6 +17,7 @@ static API: LazyLock<Api<KeyStore, PDotClient<Agent>, Polkadot>> = LazyLock::new
 });
 
 get_balance!("15FEzAVAanaAGtVZLEDMeRKdKipwQrTCpJd1k6k4WP4LhXgT");
+get_num_slashing_spans!("15FEzAVAanaAGtVZLEDMeRKdKipwQrTCpJd1k6k4WP4LhXgT");
 
 validate_xt!(staking_rebond(), "0x0713a10f");
 validate_xt!(staking_bond_extra(), "0x0701a10f");
@@ -32,6 +33,21 @@ validate_xt!(
     staking_nominate("15FEzAVAanaAGtVZLEDMeRKdKipwQrTCpJd1k6k4WP4LhXgT"),
     "0x07050400bbcd72f9f3d1782b57e512497ed7a1d3e2163333bb06c59723e28823798f5a7d"
 );
+validate_xt!(staking_validate(), "0x070402c2eb0b00");
+validate_xt!(staking_set_payee(), "0x070701");
+validate_xt!(
+    staking_payout_stakers("15FEzAVAanaAGtVZLEDMeRKdKipwQrTCpJd1k6k4WP4LhXgT"),
+    "0x0712bbcd72f9f3d1782b57e512497ed7a1d3e2163333bb06c59723e28823798f5a7de8030000"
+);
+validate_xt!(
+    staking_chill_other("15FEzAVAanaAGtVZLEDMeRKdKipwQrTCpJd1k6k4WP4LhXgT"),
+    "0x0717bbcd72f9f3d1782b57e512497ed7a1d3e2163333bb06c59723e28823798f5a7d"
+);
+validate_xt!(fast_unstake_register_fast_unstake(), "0x2800");
+validate_xt!(vesting_vest(), "0x1900");
+validate_xt!(conviction_voting_remove_vote(), "0x14040064000000");
+validate_xt!(system_remark_with_event(), "0x0007106d656d6f");
+validate_xt!(indices_free(), "0x040201000000");
 validate_xt!(
     proxy_add_proxy("15FEzAVAanaAGtVZLEDMeRKdKipwQrTCpJd1k6k4WP4LhXgT"),
     "0x1d0100bbcd72f9f3d1782b57e512497ed7a1d3e2163333bb06c59723e28823798f5a7d0300000000"
@@ -41,3 +57,10 @@ validate_xt!(
     "0x1d0200bbcd72f9f3d1782b57e512497ed7a1d3e2163333bb06c59723e28823798f5a7d0300000000"
 );
 validate_xt!(proxy_remove_proxies(), "0x1d03");
+
+#[cfg(feature = "metadata")]
+#[test]
+fn verify_metadata() {
+    let report = API.verify_metadata(None).unwrap();
+    assert!(report.is_ok(), "{report}");
+}
@@ -7,7 +7,7 @@ use pdotc::ss58::Ss58Codec;
 use pdotc::AccountId32;
 use ureq::Agent;
 
-use crate::{get_balance, validate_xt, KeyStore, PDotClient};
+use crate::{get_balance, get_num_slashing_spans, validate_xt, KeyStore, PDotClient};
 
 static CLIENT: OnceLock<PDotClient<Agent>> = OnceLock::new();
 
@@ -17,6 +17,7 @@ static API: LazyLock<Api<KeyStore, PDotClient<Agent>, Westend>> = LazyLock::new(
 });
 
 get_balance!("5Hq465EqSK865f4cHMgDpuKZf45ukuUshFxAPCCzmJEoBoNe");
+get_num_slashing_spans!("5Hq465EqSK865f4cHMgDpuKZf45ukuUshFxAPCCzmJEoBoNe");
 
 validate_xt!(staking_rebond(), "0x0613a10f");
 validate_xt!(staking_bond_extra(), "0x0601a10f");
@@ -32,6 +33,21 @@ validate_xt!(
     staking_nominate("5Hq465EqSK865f4cHMgDpuKZf45ukuUshFxAPCCzmJEoBoNe"),
     "0x06050400ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f"
 );
+validate_xt!(staking_validate(), "0x060402c2eb0b00");
+validate_xt!(staking_set_payee(), "0x060701");
+validate_xt!(
+    staking_payout_stakers("5Hq465EqSK865f4cHMgDpuKZf45ukuUshFxAPCCzmJEoBoNe"),
+    "0x0612ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0fe8030000"
+);
+validate_xt!(
+    staking_chill_other("5Hq465EqSK865f4cHMgDpuKZf45ukuUshFxAPCCzmJEoBoNe"),
+    "0x0617ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f"
+);
+validate_xt!(fast_unstake_register_fast_unstake(), "0x1e00");
+validate_xt!(vesting_vest(), "0x1300");
+validate_xt!(conviction_voting_remove_vote(), "0x1f040064000000");
+validate_xt!(system_remark_with_event(), "0x0007106d656d6f");
+validate_xt!(indices_free(), "0x030201000000");
 validate_xt!(
     proxy_add_proxy("5Hq465EqSK865f4cHMgDpuKZf45ukuUshFxAPCCzmJEoBoNe"),
     "0x160100ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f0200000000"
@@ -41,3 +57,10 @@ validate_xt!(
     "0x160200ff0011afc404c2f8c72ec8bcdeb64d6367822bf3a205a9ac4c1b17ffa75c3f0f0200000000"
 );
 validate_xt!(proxy_remove_proxies(), "0x1603");
+
+#[cfg(feature = "metadata")]
+#[test]
+fn verify_metadata() {
+    let report = API.verify_metadata(None).unwrap();
+    assert!(report.is_ok(), "{report}");
+}
@@ -2,16 +2,25 @@
 use std::str::FromStr;
 
 use pdotc::client::{Api, Signer};
-use pdotc::network::SubstrateNetwork;
+use pdotc::network::{
+    HasConvictionVoting, HasFastUnstake, HasIndices, HasProxy, HasStaking, HasSubstrateStaking,
+    HasVesting, SubstrateNetwork,
+};
 use pdotc::pallets::balances::ComposedTransfer;
+use pdotc::pallets::conviction_voting::ComposedConvictionVotingRemoveVote;
+use pdotc::pallets::fast_unstake::ComposedFastUnstakeRegisterFastUnstake;
+use pdotc::pallets::indices::ComposedIndicesFree;
 use pdotc::pallets::staking::{
-    ComposedStakingBond, ComposedStakingBondExtra, ComposedStakingChill, ComposedStakingNominate,
-    ComposedStakingRebond, ComposedStakingUnbond, ComposedStakingWithdrawUnbonded,
-    RewardDestination,
+    ComposedStakingBond, ComposedStakingBondExtra, ComposedStakingChill, ComposedStakingChillOther,
+    ComposedStakingNominate, ComposedStakingPayoutStakers, ComposedStakingRebond,
+    ComposedStakingSetPayee, ComposedStakingUnbond, ComposedStakingValidate,
+    ComposedStakingWithdrawUnbonded, RewardDestination, ValidatorPrefs,
 };
+use pdotc::pallets::system::ComposedSystemRemark;
+use pdotc::pallets::vesting::ComposedVestingVest;
 use pdotc::rpc::RpcClient;
 use pdotc::ss58::Ss58Codec;
-use pdotc::{AccountId32, MultiAddress, UncheckedExtrinsic};
+use pdotc::{AccountId32, MultiAddress, Perbill, UncheckedExtrinsic};
 
 pub fn balance_transfer<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
     api: &Api<S, Client, N>,
@@ -25,31 +34,31 @@ pub fn balance_transfer<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
     .unwrap()
 }
 
-pub fn staking_bond<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_bond<S: Signer, Client: RpcClient, N: HasStaking>(
     api: &Api<S, Client, N>,
 ) -> UncheckedExtrinsic<ComposedStakingBond> {
     api.staking_bond(1000, RewardDestination::Stash, None)
         .unwrap()
 }
-pub fn staking_bond_extra<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_bond_extra<S: Signer, Client: RpcClient, N: HasStaking>(
     api: &Api<S, Client, N>,
 ) -> UncheckedExtrinsic<ComposedStakingBondExtra> {
     api.staking_bond_extra(1000, None).unwrap()
 }
 
-pub fn staking_unbond<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_unbond<S: Signer, Client: RpcClient, N: HasStaking>(
     api: &Api<S, Client, N>,
 ) -> UncheckedExtrinsic<ComposedStakingUnbond> {
     api.staking_unbond(1000, None).unwrap()
 }
 
-pub fn staking_withdraw_unbonded<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_withdraw_unbonded<S: Signer, Client: RpcClient, N: HasStaking>(
     api: &Api<S, Client, N>,
 ) -> UncheckedExtrinsic<ComposedStakingWithdrawUnbonded> {
     api.staking_withdraw_unbonded(0, None).unwrap()
 }
 
-pub fn staking_nominate<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_nominate<S: Signer, Client: RpcClient, N: HasStaking>(
     api: &Api<S, Client, N>,
     addr: &str,
 ) -> UncheckedExtrinsic<ComposedStakingNominate> {
@@ -62,25 +71,83 @@ pub fn staking_nominate<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
     .unwrap()
 }
 
-pub fn staking_chill<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_chill<S: Signer, Client: RpcClient, N: HasStaking>(
     api: &Api<S, Client, N>,
 ) -> UncheckedExtrinsic<ComposedStakingChill> {
     api.staking_chill(None).unwrap()
 }
 
-pub fn staking_rebond<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_rebond<S: Signer, Client: RpcClient, N: HasStaking>(
     api: &Api<S, Client, N>,
 ) -> UncheckedExtrinsic<ComposedStakingRebond> {
     api.staking_rebond(1000, None).unwrap()
 }
 
-pub fn proxy_add_proxy<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn staking_validate<S: Signer, Client: RpcClient, N: HasSubstrateStaking>(
+    api: &Api<S, Client, N>,
+) -> UncheckedExtrinsic<ComposedStakingValidate> {
+    let prefs = ValidatorPrefs {
+        commission: Perbill::from_percent(5),
+        blocked: false,
+    };
+    api.staking_validate(prefs, None).unwrap()
+}
+
+pub fn staking_set_payee<S: Signer, Client: RpcClient, N: HasSubstrateStaking>(
+    api: &Api<S, Client, N>,
+) -> UncheckedExtrinsic<ComposedStakingSetPayee> {
+    api.staking_set_payee(RewardDestination::Stash, None)
+        .unwrap()
+}
+
+pub fn staking_payout_stakers<S: Signer, Client: RpcClient, N: HasSubstrateStaking>(
+    api: &Api<S, Client, N>,
+    addr: &str,
+) -> UncheckedExtrinsic<ComposedStakingPayoutStakers> {
+    api.staking_payout_stakers(
+        AccountId32::from_ss58check_with_version(addr).unwrap().0,
+        1000,
+        None,
+    )
+    .unwrap()
+}
+
+pub fn staking_chill_other<S: Signer, Client: RpcClient, N: HasSubstrateStaking>(
+    api: &Api<S, Client, N>,
+    addr: &str,
+) -> UncheckedExtrinsic<ComposedStakingChillOther> {
+    api.staking_chill_other(
+        AccountId32::from_ss58check_with_version(addr).unwrap().0,
+        None,
+    )
+    .unwrap()
+}
+
+pub fn fast_unstake_register_fast_unstake<S: Signer, Client: RpcClient, N: HasFastUnstake>(
+    api: &Api<S, Client, N>,
+) -> UncheckedExtrinsic<ComposedFastUnstakeRegisterFastUnstake> {
+    api.fast_unstake_register_fast_unstake(None).unwrap()
+}
+
+pub fn vesting_vest<S: Signer, Client: RpcClient, N: HasVesting>(
+    api: &Api<S, Client, N>,
+) -> UncheckedExtrinsic<ComposedVestingVest> {
+    api.vesting_vest(None).unwrap()
+}
+
+pub fn conviction_voting_remove_vote<S: Signer, Client: RpcClient, N: HasConvictionVoting>(
+    api: &Api<S, Client, N>,
+) -> UncheckedExtrinsic<ComposedConvictionVotingRemoveVote> {
+    api.conviction_voting_remove_vote(None, 100, None).unwrap()
+}
+
+pub fn proxy_add_proxy<S: Signer, Client: RpcClient, N: HasProxy>(
     api: &Api<S, Client, N>,
     addr: &str,
 ) -> UncheckedExtrinsic<(
     [u8; 2],
-    <N as SubstrateNetwork>::ProxyDelegateType,
-    <N as SubstrateNetwork>::ProxyTypeType,
+    <N as HasProxy>::ProxyDelegateType,
+    <N as HasProxy>::ProxyTypeType,
     u32,
 )> {
     api.add_proxy(
@@ -92,13 +159,13 @@ pub fn proxy_add_proxy<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
     .unwrap()
 }
 
-pub fn proxy_remove_proxy<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn proxy_remove_proxy<S: Signer, Client: RpcClient, N: HasProxy>(
     api: &Api<S, Client, N>,
     addr: &str,
 ) -> UncheckedExtrinsic<(
     [u8; 2],
-    <N as SubstrateNetwork>::ProxyDelegateType,
-    <N as SubstrateNetwork>::ProxyTypeType,
+    <N as HasProxy>::ProxyDelegateType,
+    <N as HasProxy>::ProxyTypeType,
     u32,
 )> {
     api.remove_proxy(
@@ -110,8 +177,21 @@ pub fn proxy_remove_proxy<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
     .unwrap()
 }
 
-pub fn proxy_remove_proxies<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+pub fn proxy_remove_proxies<S: Signer, Client: RpcClient, N: HasProxy>(
     api: &Api<S, Client, N>,
 ) -> UncheckedExtrinsic<[u8; 2]> {
     api.remove_proxies(None).unwrap()
 }
+
+pub fn system_remark_with_event<S: Signer, Client: RpcClient, N: SubstrateNetwork>(
+    api: &Api<S, Client, N>,
+) -> UncheckedExtrinsic<ComposedSystemRemark> {
+    api.system_remark_with_event(b"memo".to_vec(), None)
+        .unwrap()
+}
+
+pub fn indices_free<S: Signer, Client: RpcClient, N: HasIndices>(
+    api: &Api<S, Client, N>,
+) -> UncheckedExtrinsic<ComposedIndicesFree> {
+    api.indices_free(1, None).unwrap()
+}